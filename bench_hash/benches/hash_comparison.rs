@@ -1,91 +1,332 @@
-use std::hint::black_box;
+use std::{cell::OnceCell, hint::black_box, time::Duration};
 
-use bench_hash::hash;
-use criterion::{AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput};
-use rand::{Rng, SeedableRng, rngs::SmallRng};
+use bench_hash::{HashFunction, HashVisitor};
+use bench_support::BenchTargets;
+use criterion::{
+    AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput, measurement::Measurement,
+};
+use rand::{Rng, RngCore, SeedableRng, rngs::SmallRng};
 
 /// Input size exponents `n` for `2^n` bytes (from 16 B to 256 MiB)
 const SIZE_POW2_EXP: &[u32] = &[4, 8, 12, 16, 20, 24, 28];
 
-/// Generate random data for benchmarking.
-fn generate_data(size: usize) -> Vec<u8> {
-    let mut rng = SmallRng::seed_from_u64(42);
-    (0..size).map(|_| rng.random()).collect()
+/// Input size exponents for the non-random data patterns (from 16 B to 1 MiB).
+///
+/// Data-dependent fast paths matter most for short inputs, so these stop before the
+/// bandwidth-bound sizes to keep the total runtime reasonable.
+const PATTERN_SIZE_POW2_EXP: &[u32] = &[4, 8, 12, 16, 20];
+
+/// Shape of the bytes fed to the hashers.
+#[derive(Debug, Clone, Copy)]
+enum DataPattern {
+    /// Uniform random bytes.
+    Random,
+    /// All zero bytes, like zero-padded keys.
+    Zeros,
+    /// Printable ASCII text.
+    Ascii,
+    /// A short phrase repeated over the whole buffer.
+    Repeated,
+    /// Low-entropy structured records (`id=...,user=...,status=...;`).
+    Records,
+}
+
+impl DataPattern {
+    const ALL: &[DataPattern] = &[
+        DataPattern::Random,
+        DataPattern::Zeros,
+        DataPattern::Ascii,
+        DataPattern::Repeated,
+        DataPattern::Records,
+    ];
+
+    fn slug(self) -> &'static str {
+        match self {
+            DataPattern::Random => "random",
+            DataPattern::Zeros => "zeros",
+            DataPattern::Ascii => "ascii",
+            DataPattern::Repeated => "repeated",
+            DataPattern::Records => "records",
+        }
+    }
+
+    /// Benchmark group name, random data keeps the unsuffixed name.
+    fn group_name(self, base: &str) -> String {
+        match self {
+            DataPattern::Random => base.to_owned(),
+            _ => format!("{base}_{}", self.slug()),
+        }
+    }
+
+    fn size_exps(self) -> &'static [u32] {
+        match self {
+            DataPattern::Random => SIZE_POW2_EXP,
+            _ => PATTERN_SIZE_POW2_EXP,
+        }
+    }
+
+    /// Generate `size` bytes of this pattern for benchmarking.
+    fn generate(self, size: usize) -> Vec<u8> {
+        let mut rng = SmallRng::seed_from_u64(42);
+        match self {
+            DataPattern::Random => (0..size).map(|_| rng.random()).collect(),
+            DataPattern::Zeros => vec![0u8; size],
+            DataPattern::Ascii => {
+                const ALPHABET: &[u8] =
+                    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 .,-_/";
+                (0..size)
+                    .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())])
+                    .collect()
+            }
+            DataPattern::Repeated => b"the quick brown fox "
+                .iter()
+                .copied()
+                .cycle()
+                .take(size)
+                .collect(),
+            DataPattern::Records => {
+                let mut data = Vec::with_capacity(size + 64);
+                let mut id = 0u32;
+                while data.len() < size {
+                    let user = rng.random_range(0..1000u32);
+                    let status = if rng.random_ratio(1, 16) { "err" } else { "ok" };
+                    data.extend_from_slice(
+                        format!("id={id:08},user=user{user:04},status={status};").as_bytes(),
+                    );
+                    id += 1;
+                }
+                data.truncate(size);
+                data
+            }
+        }
+    }
+}
+
+/// Input size of the unaligned benchmarks (bytes).
+const UNALIGNED_SIZE: usize = 1 << 12;
+
+/// Largest start offset from a 64-byte aligned address in the unaligned benchmarks.
+const MAX_UNALIGNED_OFFSET: usize = 63;
+
+/// Input size exponents for the cold-cache benchmarks (from 16 B to 1 MiB).
+const COLD_SIZE_POW2_EXP: &[u32] = &[4, 8, 12, 16, 20];
+
+/// Size of the buffer pool cycled through by the cold-cache benchmarks (256 MiB).
+///
+/// This must be larger than the last-level cache of every benchmarked host.
+const COLD_POOL_BYTES: usize = 1 << 28;
+
+/// Number of keys hashed per iteration in the batch benchmarks.
+const BATCH_KEYS: usize = 10_000;
+
+type BenchGroup<'a, M> = criterion::BenchmarkGroup<'a, M>;
+
+trait BenchFn {
+    fn bench<H: HashFunction, M: Measurement>(&self, group: &mut BenchGroup<'_, M>);
+}
+
+/// Runs a [`BenchFn`] for every hash function visited from the catalog.
+struct BenchVisitor<'a, 'g, F, M: Measurement> {
+    group: &'a mut BenchGroup<'g, M>,
+    bench_fn: &'a F,
+}
+
+impl<F: BenchFn, M: Measurement> HashVisitor for BenchVisitor<'_, '_, F, M> {
+    fn visit<H: HashFunction>(&mut self) {
+        self.bench_fn.bench::<H, M>(self.group);
+    }
+}
+
+/// Hash the same buffer on every iteration, so it stays in cache.
+struct HotInput<'a> {
+    parameter: usize,
+    data: &'a [u8],
+}
+
+impl BenchFn for HotInput<'_> {
+    fn bench<H: HashFunction, M: Measurement>(&self, group: &mut BenchGroup<'_, M>) {
+        group.bench_with_input(
+            BenchmarkId::new(H::NAME, self.parameter),
+            self.data,
+            |b, data| b.iter(|| black_box(H::hash(black_box(data)))),
+        );
+    }
+}
+
+/// Hash a different `size`-byte slot of `pool` on every iteration.
+///
+/// Slots are visited in an odd-multiplier permutation so consecutive iterations are far
+/// apart and hardware prefetchers cannot follow. The pool is filled on first use, so runs
+/// that filter out the cold group never allocate it.
+struct ColdInput<'a> {
+    size: usize,
+    pool: &'a OnceCell<Vec<u8>>,
+}
+
+impl BenchFn for ColdInput<'_> {
+    fn bench<H: HashFunction, M: Measurement>(&self, group: &mut BenchGroup<'_, M>) {
+        let size = self.size;
+        let slot_size = size.next_multiple_of(64);
+        let slots = 1usize << (COLD_POOL_BYTES / slot_size).ilog2();
+        let mut i = 0usize;
+        group.bench_with_input(BenchmarkId::new(H::NAME, size), &size, |b, &size| {
+            let pool = self.pool.get_or_init(|| {
+                let mut pool = vec![0u8; COLD_POOL_BYTES];
+                SmallRng::seed_from_u64(42).fill_bytes(&mut pool);
+                pool
+            });
+            b.iter(|| {
+                let slot = i.wrapping_mul(0x9E37_79B1) & (slots - 1);
+                i = i.wrapping_add(1);
+                let start = slot * slot_size;
+                black_box(H::hash(black_box(&pool[start..start + size])))
+            })
+        });
+    }
 }
 
-fn bench_in_group<O>(
-    group: &mut criterion::BenchmarkGroup<'_, criterion::measurement::WallTime>,
-    name: &str,
-    data: &[u8],
-    f: fn(&[u8]) -> O,
+/// Hash every key of a batch per iteration, keeping all outputs so independent calls can
+/// overlap.
+struct KeyBatch<const N: usize> {
+    keys: Vec<[u8; N]>,
+}
+
+impl<const N: usize> KeyBatch<N> {
+    fn new() -> Self {
+        let data = DataPattern::Random.generate(N * BATCH_KEYS);
+        let keys = data
+            .chunks_exact(N)
+            .map(|key| key.try_into().expect("chunk has key size"))
+            .collect();
+        Self { keys }
+    }
+}
+
+impl<const N: usize> BenchFn for KeyBatch<N> {
+    fn bench<H: HashFunction, M: Measurement>(&self, group: &mut BenchGroup<'_, M>) {
+        let mut outputs = Vec::with_capacity(self.keys.len());
+        group.bench_with_input(BenchmarkId::new(H::NAME, N), &self.keys, |b, keys| {
+            b.iter(|| {
+                outputs.clear();
+                outputs.extend(black_box(keys).iter().map(|key| H::hash(key)));
+                black_box(&outputs);
+            })
+        });
+    }
+}
+
+fn bench_non_cryptographic_hashers<M: Measurement>(
+    group: &mut BenchGroup<'_, M>,
+    bench_fn: &impl BenchFn,
 ) {
-    group.bench_with_input(BenchmarkId::new(name, data.len()), data, |b, data| {
-        b.iter(|| black_box(f(black_box(data))))
-    });
+    bench_hash::visit_non_cryptographic(&mut BenchVisitor { group, bench_fn });
 }
 
-fn bench_xor128_simd_baselines(
-    group: &mut criterion::BenchmarkGroup<'_, criterion::measurement::WallTime>,
-    data: &[u8],
+fn bench_cryptographic_hashers<M: Measurement>(
+    group: &mut BenchGroup<'_, M>,
+    bench_fn: &impl BenchFn,
 ) {
-    #[cfg(target_arch = "x86_64")]
-    {
-        bench_in_group(group, "XOR-128-SSE2", data, bench_hash::xor_hash128_sse2);
-        if std::is_x86_feature_detected!("avx2") {
-            bench_in_group(group, "XOR-128-AVX2", data, bench_hash::xor_hash128_avx2);
-        }
+    bench_hash::visit_cryptographic(&mut BenchVisitor { group, bench_fn });
+}
+
+fn non_cryptographic_hash<M: Measurement>(c: &mut Criterion<M>) {
+    for &pattern in DataPattern::ALL {
+        non_cryptographic_hash_with_pattern(c, pattern);
+    }
+}
+
+fn non_cryptographic_hash_with_pattern<M: Measurement>(c: &mut Criterion<M>, pattern: DataPattern) {
+    let mut group = c.benchmark_group(pattern.group_name("non_cryptographic_hash"));
+    group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    for &exp in pattern.size_exps() {
+        let size = 1usize << exp;
+        let data = pattern.generate(size);
+        group.throughput(Throughput::Bytes(size as u64));
+        let bench_fn = HotInput {
+            parameter: size,
+            data: &data,
+        };
+        bench_non_cryptographic_hashers(&mut group, &bench_fn);
     }
 
-    #[cfg(target_arch = "aarch64")]
-    {
-        bench_in_group(group, "XOR-128-NEON", data, bench_hash::xor_hash128_neon);
+    group.finish();
+}
+
+/// Hash a fixed-size input starting at every offset in `0..=63` from a 64-byte aligned
+/// address; the benchmark parameter is the offset, so offset `0` is the aligned reference.
+fn unaligned_hash<M: Measurement>(c: &mut Criterion<M>) {
+    let mut group = c.benchmark_group("unaligned_hash");
+    // 64 offsets per hasher, so keep each case short.
+    group.warm_up_time(Duration::from_millis(500));
+    group.measurement_time(Duration::from_secs(1));
+    group.throughput(Throughput::Bytes(UNALIGNED_SIZE as u64));
+
+    let buffer = DataPattern::Random.generate(UNALIGNED_SIZE + 2 * (MAX_UNALIGNED_OFFSET + 1));
+    let base = buffer.as_ptr().align_offset(MAX_UNALIGNED_OFFSET + 1);
+    for offset in 0..=MAX_UNALIGNED_OFFSET {
+        let start = base + offset;
+        let bench_fn = HotInput {
+            parameter: offset,
+            data: &buffer[start..start + UNALIGNED_SIZE],
+        };
+        bench_non_cryptographic_hashers(&mut group, &bench_fn);
     }
+
+    group.finish();
 }
 
-fn non_cryptographic_hash(c: &mut Criterion) {
-    let mut group = c.benchmark_group("non_cryptographic_hash");
+/// Same hashers and sizes as the hot groups, but every iteration reads a slot of a pool
+/// larger than the last-level cache.
+fn cold_cache_hash<M: Measurement>(c: &mut Criterion<M>) {
+    let mut group = c.benchmark_group("cold_cache_hash");
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
-    for &exp in SIZE_POW2_EXP {
+    let pool = OnceCell::new();
+    for &exp in COLD_SIZE_POW2_EXP {
         let size = 1usize << exp;
-        let data = generate_data(size);
-        let data = data.as_slice();
         group.throughput(Throughput::Bytes(size as u64));
-        bench_in_group(&mut group, "XOR-64-ILP", data, bench_hash::xor_hash64);
-        bench_xor128_simd_baselines(&mut group, data);
-
-        bench_in_group(
-            &mut group,
-            "RAPIDHASH-64",
-            data,
-            rapidhash::v3::rapidhash_v3,
-        );
-        bench_in_group(&mut group, "XXH3-64", data, xxhash_rust::xxh3::xxh3_64);
-        bench_in_group(&mut group, "XXH3-128", data, xxhash_rust::xxh3::xxh3_128);
-        bench_in_group(&mut group, "GXHASH-64", data, |input| {
-            gxhash::gxhash64(input, 0)
-        });
-        bench_in_group(&mut group, "GXHASH-128", data, |input| {
-            gxhash::gxhash128(input, 0)
-        });
+        let bench_fn = ColdInput { size, pool: &pool };
+        bench_non_cryptographic_hashers(&mut group, &bench_fn);
+        bench_cryptographic_hashers(&mut group, &bench_fn);
     }
 
     group.finish();
 }
 
-fn cryptographic_hash(c: &mut Criterion) {
-    let mut group = c.benchmark_group("cryptographic_hash");
+/// Hash batches of short fixed-size keys, reporting keys per second; the benchmark parameter
+/// is the key size in bytes.
+fn batch_small_keys<M: Measurement>(c: &mut Criterion<M>) {
+    let mut group = c.benchmark_group("batch_small_keys");
+    group.throughput(Throughput::Elements(BATCH_KEYS as u64));
+
+    bench_non_cryptographic_hashers(&mut group, &KeyBatch::<8>::new());
+    bench_non_cryptographic_hashers(&mut group, &KeyBatch::<16>::new());
+    bench_non_cryptographic_hashers(&mut group, &KeyBatch::<32>::new());
+    bench_non_cryptographic_hashers(&mut group, &KeyBatch::<64>::new());
+
+    group.finish();
+}
+
+fn cryptographic_hash<M: Measurement>(c: &mut Criterion<M>) {
+    for &pattern in DataPattern::ALL {
+        cryptographic_hash_with_pattern(c, pattern);
+    }
+}
+
+fn cryptographic_hash_with_pattern<M: Measurement>(c: &mut Criterion<M>, pattern: DataPattern) {
+    let mut group = c.benchmark_group(pattern.group_name("cryptographic_hash"));
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
-    for &exp in SIZE_POW2_EXP {
+    for &exp in pattern.size_exps() {
         let size = 1usize << exp;
-        let data = generate_data(size);
-        let data = data.as_slice();
+        let data = pattern.generate(size);
         group.throughput(Throughput::Bytes(size as u64));
-        bench_in_group(&mut group, "SHA2-256", data, hash::<sha2::Sha256>);
-        bench_in_group(&mut group, "SHA2-512", data, hash::<sha2::Sha512>);
-        bench_in_group(&mut group, "BLAKE3-256", data, blake3::hash);
-        bench_in_group(&mut group, "BLAKE2B-512", data, hash::<blake2::Blake2b512>);
+        let bench_fn = HotInput {
+            parameter: size,
+            data: &data,
+        };
+        bench_cryptographic_hashers(&mut group, &bench_fn);
     }
 
     group.finish();
@@ -95,9 +336,18 @@ fn criterion_config() -> Criterion {
     Criterion::default()
 }
 
-criterion::criterion_group! {
-    name = benches;
-    config = criterion_config();
-    targets = non_cryptographic_hash, cryptographic_hash
+struct HashBenches;
+
+impl BenchTargets for HashBenches {
+    fn run<M: Measurement>(&self, c: &mut Criterion<M>) {
+        non_cryptographic_hash(c);
+        unaligned_hash(c);
+        batch_small_keys(c);
+        cryptographic_hash(c);
+        cold_cache_hash(c);
+    }
+}
+
+fn main() {
+    bench_support::bench_main(criterion_config, &HashBenches);
 }
-criterion::criterion_main!(benches);
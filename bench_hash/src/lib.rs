@@ -1,9 +1,549 @@
+use std::{fmt, marker::PhantomData};
+
 pub fn hash<D: digest::Digest>(data: &[u8]) -> digest::Output<D> {
     let mut hasher = D::new();
     hasher.update(data);
     hasher.finalize()
 }
 
+/// Seed or key input accepted by a hash function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seeding {
+    /// No seed or key input.
+    Unseeded,
+    /// Accepts a 64-bit seed, see [`HashFunction::hash_seeded`].
+    Seeded,
+    /// Accepts a 256-bit secret key (MAC mode), see [`HashFunction::hash_keyed`].
+    Keyed,
+}
+
+/// Incremental hashing state.
+///
+/// Feeding the same bytes in any split must produce the same output as the one-shot
+/// [`HashFunction::hash`].
+pub trait StreamingHash {
+    type Output;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Self::Output;
+}
+
+/// A benchmarked hash function with its output normalized to little-endian bytes.
+pub trait HashFunction {
+    /// Label used in benchmark ids and reports, e.g. `XXH3-64`.
+    const NAME: &'static str;
+    const CRYPTOGRAPHIC: bool;
+    const SEEDING: Seeding;
+    /// Whether [`Self::Streaming`] is the hasher's own incremental state rather than a
+    /// buffer that is hashed in one shot on finalize.
+    const NATIVE_STREAMING: bool;
+    const OUTPUT_BITS: u32 = (size_of::<Self::Output>() * 8) as u32;
+
+    /// `[u8; OUTPUT_BITS / 8]`, little-endian for integer outputs.
+    type Output: AsRef<[u8]> + Copy + Eq + fmt::Debug;
+    type Streaming: StreamingHash<Output = Self::Output>;
+
+    fn hash(data: &[u8]) -> Self::Output;
+
+    /// Hash with a 64-bit seed, `None` unless [`Self::SEEDING`] is [`Seeding::Seeded`].
+    fn hash_seeded(_seed: u64, _data: &[u8]) -> Option<Self::Output> {
+        None
+    }
+
+    /// Hash with a 256-bit key, `None` unless [`Self::SEEDING`] is [`Seeding::Keyed`].
+    fn hash_keyed(_key: &[u8; 32], _data: &[u8]) -> Option<Self::Output> {
+        None
+    }
+
+    fn streaming() -> Self::Streaming;
+}
+
+/// Static description of a [`HashFunction`], for iterating the catalog at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashInfo {
+    pub name: &'static str,
+    pub output_bits: u32,
+    pub cryptographic: bool,
+    pub seeding: Seeding,
+    pub native_streaming: bool,
+}
+
+impl HashInfo {
+    pub fn of<H: HashFunction>() -> Self {
+        Self {
+            name: H::NAME,
+            output_bits: H::OUTPUT_BITS,
+            cryptographic: H::CRYPTOGRAPHIC,
+            seeding: H::SEEDING,
+            native_streaming: H::NATIVE_STREAMING,
+        }
+    }
+}
+
+/// Callback invoked once per hash function of the catalog.
+pub trait HashVisitor {
+    fn visit<H: HashFunction>(&mut self);
+}
+
+/// Visit every non-cryptographic hash function, XOR baselines first.
+///
+/// SIMD baselines are only visited when compiled for the current target.
+pub fn visit_non_cryptographic(visitor: &mut impl HashVisitor) {
+    visitor.visit::<XorHash64>();
+    #[cfg(target_arch = "x86_64")]
+    visitor.visit::<XorHash128Sse2>();
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    visitor.visit::<XorHash128Avx2>();
+    #[cfg(target_arch = "aarch64")]
+    visitor.visit::<XorHash128Neon>();
+    visitor.visit::<RapidHash64>();
+    visitor.visit::<Xxh3Hash64>();
+    visitor.visit::<Xxh3Hash128>();
+    visitor.visit::<GxHash64>();
+    visitor.visit::<GxHash128>();
+}
+
+/// Visit every cryptographic hash function.
+pub fn visit_cryptographic(visitor: &mut impl HashVisitor) {
+    visitor.visit::<Sha2Hash256>();
+    visitor.visit::<Sha2Hash512>();
+    visitor.visit::<Blake3Hash256>();
+    visitor.visit::<Blake2bHash512>();
+}
+
+pub fn visit_all(visitor: &mut impl HashVisitor) {
+    visit_non_cryptographic(visitor);
+    visit_cryptographic(visitor);
+}
+
+/// Descriptions of every hash function in visiting order.
+pub fn catalog() -> Vec<HashInfo> {
+    struct Collect(Vec<HashInfo>);
+
+    impl HashVisitor for Collect {
+        fn visit<H: HashFunction>(&mut self) {
+            self.0.push(HashInfo::of::<H>());
+        }
+    }
+
+    let mut collect = Collect(Vec::new());
+    visit_all(&mut collect);
+    collect.0
+}
+
+/// Streaming adapter for one-shot-only hash functions: buffers input, hashes on finalize.
+#[derive(Debug, Clone)]
+pub struct Buffered<H> {
+    buffer: Vec<u8>,
+    _hash: PhantomData<H>,
+}
+
+impl<H> Default for Buffered<H> {
+    fn default() -> Self {
+        Self {
+            buffer: Vec::new(),
+            _hash: PhantomData,
+        }
+    }
+}
+
+impl<H: HashFunction> StreamingHash for Buffered<H> {
+    type Output = H::Output;
+
+    fn update(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        H::hash(&self.buffer)
+    }
+}
+
+/// Streaming state of a [`digest::Digest`] with an `N`-byte output.
+#[derive(Debug, Clone, Default)]
+pub struct DigestStreaming<D, const N: usize>(D);
+
+impl<D: digest::Digest, const N: usize> StreamingHash for DigestStreaming<D, N> {
+    type Output = [u8; N];
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        digest_array(&self.0.finalize())
+    }
+}
+
+fn digest_array<const N: usize>(output: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(output);
+    out
+}
+
+/// `XOR-64-ILP`, see [`xor_hash64`].
+pub struct XorHash64;
+
+impl HashFunction for XorHash64 {
+    type Output = [u8; 8];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XOR-64-ILP";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xor_hash64(data).to_le_bytes()
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `XOR-128-SSE2`, see [`xor_hash128_sse2`].
+#[cfg(target_arch = "x86_64")]
+pub struct XorHash128Sse2;
+
+#[cfg(target_arch = "x86_64")]
+impl HashFunction for XorHash128Sse2 {
+    type Output = [u8; 16];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XOR-128-SSE2";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xor_hash128_sse2(data).to_le_bytes()
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `XOR-128-AVX2`, see [`xor_hash128_avx2`].
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+pub struct XorHash128Avx2;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+impl HashFunction for XorHash128Avx2 {
+    type Output = [u8; 16];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XOR-128-AVX2";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xor_hash128_avx2(data).to_le_bytes()
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `XOR-128-NEON`, see [`xor_hash128_neon`].
+#[cfg(target_arch = "aarch64")]
+pub struct XorHash128Neon;
+
+#[cfg(target_arch = "aarch64")]
+impl HashFunction for XorHash128Neon {
+    type Output = [u8; 16];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XOR-128-NEON";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xor_hash128_neon(data).to_le_bytes()
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `RAPIDHASH-64`: rapidhash v3.
+///
+/// The crate's `std::hash::Hasher` produces different output than the one-shot function,
+/// so streaming is buffered.
+pub struct RapidHash64;
+
+impl HashFunction for RapidHash64 {
+    type Output = [u8; 8];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "RAPIDHASH-64";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Seeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        rapidhash::v3::rapidhash_v3(data).to_le_bytes()
+    }
+
+    fn hash_seeded(seed: u64, data: &[u8]) -> Option<Self::Output> {
+        let secrets = rapidhash::v3::RapidSecrets::seed(seed);
+        Some(rapidhash::v3::rapidhash_v3_seeded(data, &secrets).to_le_bytes())
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `XXH3-64`.
+pub struct Xxh3Hash64;
+
+/// Streaming state of [`Xxh3Hash64`].
+#[derive(Clone, Default)]
+pub struct Xxh3Streaming64(xxhash_rust::xxh3::Xxh3Default);
+
+impl StreamingHash for Xxh3Streaming64 {
+    type Output = [u8; 8];
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.digest().to_le_bytes()
+    }
+}
+
+impl HashFunction for Xxh3Hash64 {
+    type Output = [u8; 8];
+    type Streaming = Xxh3Streaming64;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XXH3-64";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Seeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xxhash_rust::xxh3::xxh3_64(data).to_le_bytes()
+    }
+
+    fn hash_seeded(seed: u64, data: &[u8]) -> Option<Self::Output> {
+        Some(xxhash_rust::xxh3::xxh3_64_with_seed(data, seed).to_le_bytes())
+    }
+
+    fn streaming() -> Self::Streaming {
+        Xxh3Streaming64::default()
+    }
+}
+
+/// `XXH3-128`.
+pub struct Xxh3Hash128;
+
+/// Streaming state of [`Xxh3Hash128`].
+#[derive(Clone, Default)]
+pub struct Xxh3Streaming128(xxhash_rust::xxh3::Xxh3Default);
+
+impl StreamingHash for Xxh3Streaming128 {
+    type Output = [u8; 16];
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.digest128().to_le_bytes()
+    }
+}
+
+impl HashFunction for Xxh3Hash128 {
+    type Output = [u8; 16];
+    type Streaming = Xxh3Streaming128;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "XXH3-128";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Seeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        xxhash_rust::xxh3::xxh3_128(data).to_le_bytes()
+    }
+
+    fn hash_seeded(seed: u64, data: &[u8]) -> Option<Self::Output> {
+        Some(xxhash_rust::xxh3::xxh3_128_with_seed(data, seed).to_le_bytes())
+    }
+
+    fn streaming() -> Self::Streaming {
+        Xxh3Streaming128::default()
+    }
+}
+
+/// `GXHASH-64` with seed `0`.
+///
+/// `GxHasher` produces different output than the one-shot function, so streaming is
+/// buffered.
+pub struct GxHash64;
+
+impl HashFunction for GxHash64 {
+    type Output = [u8; 8];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "GXHASH-64";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Seeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        gxhash::gxhash64(data, 0).to_le_bytes()
+    }
+
+    fn hash_seeded(seed: u64, data: &[u8]) -> Option<Self::Output> {
+        Some(gxhash::gxhash64(data, seed as i64).to_le_bytes())
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `GXHASH-128` with seed `0`, streaming is buffered like [`GxHash64`].
+pub struct GxHash128;
+
+impl HashFunction for GxHash128 {
+    type Output = [u8; 16];
+    type Streaming = Buffered<Self>;
+
+    const CRYPTOGRAPHIC: bool = false;
+    const NAME: &'static str = "GXHASH-128";
+    const NATIVE_STREAMING: bool = false;
+    const SEEDING: Seeding = Seeding::Seeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        gxhash::gxhash128(data, 0).to_le_bytes()
+    }
+
+    fn hash_seeded(seed: u64, data: &[u8]) -> Option<Self::Output> {
+        Some(gxhash::gxhash128(data, seed as i64).to_le_bytes())
+    }
+
+    fn streaming() -> Self::Streaming {
+        Buffered::default()
+    }
+}
+
+/// `SHA2-256`.
+pub struct Sha2Hash256;
+
+impl HashFunction for Sha2Hash256 {
+    type Output = [u8; 32];
+    type Streaming = DigestStreaming<sha2::Sha256, 32>;
+
+    const CRYPTOGRAPHIC: bool = true;
+    const NAME: &'static str = "SHA2-256";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        digest_array(&hash::<sha2::Sha256>(data))
+    }
+
+    fn streaming() -> Self::Streaming {
+        DigestStreaming::default()
+    }
+}
+
+/// `SHA2-512`.
+pub struct Sha2Hash512;
+
+impl HashFunction for Sha2Hash512 {
+    type Output = [u8; 64];
+    type Streaming = DigestStreaming<sha2::Sha512, 64>;
+
+    const CRYPTOGRAPHIC: bool = true;
+    const NAME: &'static str = "SHA2-512";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Unseeded;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        digest_array(&hash::<sha2::Sha512>(data))
+    }
+
+    fn streaming() -> Self::Streaming {
+        DigestStreaming::default()
+    }
+}
+
+/// `BLAKE3-256`, keyed through `blake3::keyed_hash`.
+pub struct Blake3Hash256;
+
+impl StreamingHash for blake3::Hasher {
+    type Output = [u8; 32];
+
+    fn update(&mut self, data: &[u8]) {
+        blake3::Hasher::update(self, data);
+    }
+
+    fn finalize(self) -> Self::Output {
+        blake3::Hasher::finalize(&self).into()
+    }
+}
+
+impl HashFunction for Blake3Hash256 {
+    type Output = [u8; 32];
+    type Streaming = blake3::Hasher;
+
+    const CRYPTOGRAPHIC: bool = true;
+    const NAME: &'static str = "BLAKE3-256";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Keyed;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        blake3::hash(data).into()
+    }
+
+    fn hash_keyed(key: &[u8; 32], data: &[u8]) -> Option<Self::Output> {
+        Some(blake3::keyed_hash(key, data).into())
+    }
+
+    fn streaming() -> Self::Streaming {
+        blake3::Hasher::new()
+    }
+}
+
+/// `BLAKE2B-512`, keyed through `blake2::Blake2bMac512`.
+pub struct Blake2bHash512;
+
+impl HashFunction for Blake2bHash512 {
+    type Output = [u8; 64];
+    type Streaming = DigestStreaming<blake2::Blake2b512, 64>;
+
+    const CRYPTOGRAPHIC: bool = true;
+    const NAME: &'static str = "BLAKE2B-512";
+    const NATIVE_STREAMING: bool = true;
+    const SEEDING: Seeding = Seeding::Keyed;
+
+    fn hash(data: &[u8]) -> Self::Output {
+        digest_array(&hash::<blake2::Blake2b512>(data))
+    }
+
+    fn hash_keyed(key: &[u8; 32], data: &[u8]) -> Option<Self::Output> {
+        use digest::Mac;
+
+        let mut mac = <blake2::Blake2bMac512 as Mac>::new_from_slice(key).ok()?;
+        mac.update(data);
+        Some(digest_array(&mac.finalize().into_bytes()))
+    }
+
+    fn streaming() -> Self::Streaming {
+        DigestStreaming::default()
+    }
+}
+
 /// Extremely simple 64-bit XOR fold hash.
 ///
 /// This is intended as a lightweight upper-bound style baseline for throughput experiments.
@@ -48,6 +588,41 @@ pub fn xor_hash64(data: &[u8]) -> u64 {
     acc
 }
 
+/// Safe scalar reference for [`xor_hash64`]: XOR of every little-endian 8-byte word, with
+/// the tail zero-padded.
+pub fn xor_hash64_reference(data: &[u8]) -> u64 {
+    data.chunks(8).fold(0, |acc, chunk| {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        acc ^ u64::from_le_bytes(word)
+    })
+}
+
+/// Safe scalar reference for the `xor_hash128*` variants: XOR of every little-endian
+/// 16-byte block, with the tail zero-padded.
+pub fn xor_hash128_reference(data: &[u8]) -> u128 {
+    data.chunks(16).fold(0, |acc, chunk| {
+        let mut block = [0u8; 16];
+        block[..chunk.len()].copy_from_slice(chunk);
+        acc ^ u128::from_le_bytes(block)
+    })
+}
+
+pub type XorHash128Fn = fn(&[u8]) -> u128;
+
+/// Every 128-bit XOR variant compiled for this target, including the dispatching
+/// [`xor_hash128`].
+pub fn xor_hash128_variants() -> Vec<(&'static str, XorHash128Fn)> {
+    let mut variants: Vec<(&'static str, XorHash128Fn)> = vec![("xor_hash128", xor_hash128)];
+    #[cfg(target_arch = "x86_64")]
+    variants.push(("xor_hash128_sse2", xor_hash128_sse2));
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    variants.push(("xor_hash128_avx2", xor_hash128_avx2));
+    #[cfg(target_arch = "aarch64")]
+    variants.push(("xor_hash128_neon", xor_hash128_neon));
+    variants
+}
+
 /// 128-bit XOR fold hash optimized as a throughput baseline.
 ///
 /// Uses SIMD XOR with multiple independent accumulators on modern x86_64/aarch64 targets.
@@ -216,3 +791,245 @@ pub fn xor_hash128_neon(data: &[u8]) -> u128 {
 
     u128::from_le_bytes(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, RngCore, SeedableRng, rngs::SmallRng};
+
+    use super::{
+        HashFunction, HashVisitor, Seeding, StreamingHash, catalog, visit_all, xor_hash64,
+        xor_hash64_reference, xor_hash128_reference, xor_hash128_variants,
+    };
+
+    fn assert_xor_variants_match(data: &[u8], context: &str) {
+        assert_eq!(
+            xor_hash64(data),
+            xor_hash64_reference(data),
+            "xor_hash64 {context}"
+        );
+        let expected = xor_hash128_reference(data);
+        for (name, f) in xor_hash128_variants() {
+            assert_eq!(f(data), expected, "{name} {context}");
+        }
+    }
+
+    #[test]
+    fn xor_references_fold_padded_words() {
+        assert_eq!(xor_hash64_reference(b""), 0);
+        assert_eq!(
+            xor_hash64_reference(b"abcdefghij"),
+            u64::from_le_bytes(*b"abcdefgh") ^ u64::from_le_bytes(*b"ij\0\0\0\0\0\0")
+        );
+        assert_eq!(xor_hash128_reference(&[0xff; 32]), 0);
+        assert_eq!(xor_hash128_reference(&[1]), 1);
+    }
+
+    /// Small enough to run under Miri, which checks the unaligned reads of the scalar path.
+    #[test]
+    fn xor_hash64_matches_reference_for_short_inputs() {
+        let buffer: Vec<u8> = (0..80).map(|i| (i * 37 % 251) as u8).collect();
+        for offset in 0..8 {
+            for len in 0..=72 {
+                let data = &buffer[offset..offset + len];
+                assert_eq!(
+                    xor_hash64(data),
+                    xor_hash64_reference(data),
+                    "len {len} offset {offset}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "SIMD and external hashers are not Miri-compatible")]
+    fn xor_variants_match_reference_for_all_lengths_and_offsets() {
+        let mut buffer = vec![0u8; 300 + 64];
+        SmallRng::seed_from_u64(42).fill_bytes(&mut buffer);
+        for offset in 0..64 {
+            for len in 0..=300 {
+                let data = &buffer[offset..offset + len];
+                assert_xor_variants_match(data, &format!("len {len} offset {offset}"));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "SIMD and external hashers are not Miri-compatible")]
+    fn xor_variants_match_reference_on_random_inputs() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut buffer = vec![0u8; 8192 + 64];
+        for case in 0..256 {
+            rng.fill_bytes(&mut buffer);
+            let offset = rng.random_range(0..64);
+            let len = rng.random_range(0..=8192);
+            let data = &buffer[offset..offset + len];
+            assert_xor_variants_match(data, &format!("case {case} len {len} offset {offset}"));
+        }
+    }
+
+    /// Known answers as hex of the normalized output (little-endian for integer hashes), for
+    /// the inputs `""`, `"abc"` and the bytes `0..=255`.
+    const KNOWN_ANSWERS: &[(&str, [&str; 3])] = &[
+        ("RAPIDHASH-64", [
+            "aecdcee24bdc3803",
+            "a20d9cfaea5b47cb",
+            "9d0b26b9f483f3a2",
+        ]),
+        ("XXH3-64", [
+            "c294d3380580062d",
+            "50392f89945faf78",
+            "712d953b43a40894",
+        ]),
+        ("XXH3-128", [
+            "7f498d4624c30160d8984701d306aa99",
+            "50392f89945faf7885613a73b65ab006",
+            "712d953b43a40894c39a84503fa9f8f1",
+        ]),
+        ("GXHASH-64", [
+            "3fe8ff96436cd9ee",
+            "0454cceefaa64377",
+            "5e270621d8cb11e5",
+        ]),
+        ("GXHASH-128", [
+            "3fe8ff96436cd9eeb4d8545d81d8c6e3",
+            "0454cceefaa6437736d9d0dd266baef7",
+            "5e270621d8cb11e53dde32150541cefa",
+        ]),
+        ("SHA2-256", [
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "40aff2e9d2d8922e47afd4648e6967497158785fbd1da870e7110266bf944880",
+        ]),
+        ("SHA2-512", [
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+             47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "1e7b80bc8edc552c8feeb2780e111477e5bc70465fac1a77b29b35980c3f0ce4\
+             a036a6c9462036824bd56801e62af7e9feba5c22ed8a5af877bf7de117dcac6d",
+        ]),
+        ("BLAKE3-256", [
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            "4a495ba42461748eca8fdad618f976aa726cc2903de9fcb40735a786ac1c196b",
+        ]),
+        ("BLAKE2B-512", [
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+             d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce",
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            "1ecc896f34d3f9cac484c73f75f6a5fb58ee6784be41b35f46067b9c65c63a67\
+             94d3d744112c653f73dd7deb6666204c5a9bfa5b46081fc10fdbe7884fa5cbf8",
+        ]),
+    ];
+
+    struct CheckKnownAnswers {
+        checked: usize,
+    }
+
+    impl HashVisitor for CheckKnownAnswers {
+        fn visit<H: HashFunction>(&mut self) {
+            if H::NAME.starts_with("XOR-") {
+                return;
+            }
+            let (_, expected) = KNOWN_ANSWERS
+                .iter()
+                .find(|(name, _)| *name == H::NAME)
+                .unwrap_or_else(|| panic!("missing known answers for {}", H::NAME));
+
+            let long: Vec<u8> = (0..=255).collect();
+            let inputs: [&[u8]; 3] = [b"", b"abc", &long];
+            for (input, expected) in inputs.iter().zip(expected) {
+                let hex: String = H::hash(input)
+                    .as_ref()
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect();
+                assert_eq!(&hex, expected, "{} on {} bytes", H::NAME, input.len());
+            }
+            self.checked += 1;
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "SIMD and external hashers are not Miri-compatible")]
+    fn external_hashers_match_known_answers() {
+        let mut check = CheckKnownAnswers { checked: 0 };
+        visit_all(&mut check);
+        assert_eq!(check.checked, KNOWN_ANSWERS.len());
+    }
+
+    #[test]
+    fn catalog_names_are_unique() {
+        let catalog = catalog();
+        for (i, info) in catalog.iter().enumerate() {
+            assert!(
+                catalog[i + 1..].iter().all(|other| other.name != info.name),
+                "duplicate name: {}",
+                info.name
+            );
+        }
+    }
+
+    #[test]
+    fn catalog_splits_by_cryptographic_flag() {
+        let catalog = catalog();
+        let first_crypto = catalog
+            .iter()
+            .position(|info| info.cryptographic)
+            .expect("catalog should contain cryptographic hashes");
+        assert!(
+            catalog[first_crypto..]
+                .iter()
+                .all(|info| info.cryptographic)
+        );
+    }
+
+    struct CheckContract;
+
+    impl HashVisitor for CheckContract {
+        fn visit<H: HashFunction>(&mut self) {
+            let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 % 251) as u8).collect();
+            let output = H::hash(&data);
+            assert_eq!(
+                output.as_ref().len() * 8,
+                H::OUTPUT_BITS as usize,
+                "{}",
+                H::NAME
+            );
+
+            for split in [0, 1, 7, 64, 999, 1000] {
+                let mut streaming = H::streaming();
+                streaming.update(&data[..split]);
+                streaming.update(&data[split..]);
+                assert_eq!(streaming.finalize(), output, "{} split at {split}", H::NAME);
+            }
+
+            let seeded = H::hash_seeded(7, &data);
+            assert_eq!(
+                seeded.is_some(),
+                H::SEEDING == Seeding::Seeded,
+                "{}",
+                H::NAME
+            );
+
+            let keyed = H::hash_keyed(&[7; 32], &data);
+            assert_eq!(keyed.is_some(), H::SEEDING == Seeding::Keyed, "{}", H::NAME);
+            if let Some(keyed) = keyed {
+                assert_ne!(keyed, output, "{} keyed", H::NAME);
+                assert_ne!(
+                    H::hash_keyed(&[8; 32], &data),
+                    Some(keyed),
+                    "{} key ignored",
+                    H::NAME
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "SIMD and external hashers are not Miri-compatible")]
+    fn every_hash_function_keeps_its_contract() {
+        visit_all(&mut CheckContract);
+    }
+}
@@ -1,8 +1,8 @@
 use std::hint::black_box;
 
+use bench_support::BenchTargets;
 use criterion::{
-    AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput, criterion_group,
-    criterion_main,
+    AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput, measurement::Measurement,
 };
 use rand::{RngCore, SeedableRng};
 
@@ -18,14 +18,14 @@ pub const SIZES_BYTES: &[usize] = &[
 const U64_BATCH_COUNTS: &[usize] = &[1, 16, 64, 256, 1024];
 const SEED: u64 = 42;
 
-type BenchGroup<'a> = criterion::BenchmarkGroup<'a, criterion::measurement::WallTime>;
+type BenchGroup<'a, M> = criterion::BenchmarkGroup<'a, M>;
 
 trait BenchFn {
-    fn set_throughput(&self, group: &mut BenchGroup<'_>);
-    fn bench<R: RngCore>(&self, group: &mut BenchGroup<'_>, name: &str, rng: R);
+    fn set_throughput<M: Measurement>(&self, group: &mut BenchGroup<'_, M>);
+    fn bench<R: RngCore, M: Measurement>(&self, group: &mut BenchGroup<'_, M>, name: &str, rng: R);
 }
 
-fn bench_all_prngs(group: &mut BenchGroup<'_>, bench_fn: &impl BenchFn) {
+fn bench_all_prngs<M: Measurement>(group: &mut BenchGroup<'_, M>, bench_fn: &impl BenchFn) {
     bench_fn.set_throughput(group);
     bench_fn.bench(group, "PCG64", rand_pcg::Pcg64::seed_from_u64(SEED));
     bench_fn.bench(group, "PCG64-MCG", rand_pcg::Pcg64Mcg::seed_from_u64(SEED));
@@ -47,11 +47,16 @@ struct U64Gen {
 }
 
 impl BenchFn for U64Gen {
-    fn set_throughput(&self, group: &mut BenchGroup<'_>) {
+    fn set_throughput<M: Measurement>(&self, group: &mut BenchGroup<'_, M>) {
         group.throughput(Throughput::Elements(self.count as u64));
     }
 
-    fn bench<R: RngCore>(&self, group: &mut BenchGroup<'_>, name: &str, mut rng: R) {
+    fn bench<R: RngCore, M: Measurement>(
+        &self,
+        group: &mut BenchGroup<'_, M>,
+        name: &str,
+        mut rng: R,
+    ) {
         let count = self.count;
         let mut acc = 0u64;
         group.bench_with_input(BenchmarkId::new(name, count), &count, |b, &count| {
@@ -70,11 +75,16 @@ struct FillBytes {
 }
 
 impl BenchFn for FillBytes {
-    fn set_throughput(&self, group: &mut BenchGroup<'_>) {
+    fn set_throughput<M: Measurement>(&self, group: &mut BenchGroup<'_, M>) {
         group.throughput(Throughput::Bytes(self.size as u64));
     }
 
-    fn bench<R: RngCore>(&self, group: &mut BenchGroup<'_>, name: &str, mut rng: R) {
+    fn bench<R: RngCore, M: Measurement>(
+        &self,
+        group: &mut BenchGroup<'_, M>,
+        name: &str,
+        mut rng: R,
+    ) {
         let size = self.size;
         let mut buf = vec![0u8; size];
         group.bench_with_input(BenchmarkId::new(name, size), &size, |b, &_size| {
@@ -86,7 +96,7 @@ impl BenchFn for FillBytes {
     }
 }
 
-fn u64_generation(c: &mut Criterion) {
+fn u64_generation<M: Measurement>(c: &mut Criterion<M>) {
     let mut group = c.benchmark_group("u64_generation");
 
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
@@ -99,7 +109,7 @@ fn u64_generation(c: &mut Criterion) {
     group.finish();
 }
 
-fn bytes_generation(c: &mut Criterion) {
+fn bytes_generation<M: Measurement>(c: &mut Criterion<M>) {
     let mut group = c.benchmark_group("bytes_generation");
     group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
 
@@ -115,9 +125,15 @@ fn criterion_config() -> Criterion {
     Criterion::default()
 }
 
-criterion_group! {
-    name = benches;
-    config = criterion_config();
-    targets = u64_generation, bytes_generation
+struct RngBenches;
+
+impl BenchTargets for RngBenches {
+    fn run<M: Measurement>(&self, c: &mut Criterion<M>) {
+        u64_generation(c);
+        bytes_generation(c);
+    }
+}
+
+fn main() {
+    bench_support::bench_main(criterion_config, &RngBenches);
 }
-criterion_main!(benches);
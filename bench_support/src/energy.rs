@@ -0,0 +1,238 @@
+//! Energy measurement from Linux RAPL (Running Average Power Limit) counters.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use criterion::{
+    Throughput,
+    measurement::{Measurement, ValueFormatter},
+};
+
+const POWERCAP_DIR: &str = "/sys/class/powercap";
+
+/// Criterion measurement reading the package energy counters under
+/// `/sys/class/powercap/intel-rapl:N` (AMD exposes the same interface).
+///
+/// Values are microjoules per iteration, summed over all packages. Sub-domains
+/// (`intel-rapl:N:M`) are already included in their package and are not read.
+#[derive(Debug)]
+pub struct RaplEnergy {
+    domains: Vec<RaplDomain>,
+}
+
+#[derive(Debug)]
+struct RaplDomain {
+    energy_path: PathBuf,
+    /// Counter value at which `energy_uj` wraps around to zero.
+    max_energy_uj: u64,
+}
+
+impl RaplEnergy {
+    /// Find readable package domains, or explain why energy cannot be measured.
+    pub fn detect() -> Result<Self, String> {
+        Self::detect_in(Path::new(POWERCAP_DIR))
+    }
+
+    fn detect_in(powercap_dir: &Path) -> Result<Self, String> {
+        let entries = fs::read_dir(powercap_dir)
+            .map_err(|error| format!("cannot read {}: {error}", powercap_dir.display()))?;
+
+        let mut domains = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !is_package_domain(&name) {
+                continue;
+            }
+            let dir = entry.path();
+            let energy_path = dir.join("energy_uj");
+            if read_counter(&energy_path).is_none() {
+                return Err(format!(
+                    "cannot read {} (reading RAPL counters usually requires root)",
+                    energy_path.display()
+                ));
+            }
+            let max_energy_uj = read_counter(&dir.join("max_energy_range_uj")).unwrap_or(u64::MAX);
+            domains.push(RaplDomain {
+                energy_path,
+                max_energy_uj,
+            });
+        }
+
+        if domains.is_empty() {
+            return Err(format!(
+                "no RAPL package domains found in {}",
+                powercap_dir.display()
+            ));
+        }
+        domains.sort_by(|a, b| a.energy_path.cmp(&b.energy_path));
+        Ok(Self { domains })
+    }
+
+    fn read_all(&self) -> Vec<u64> {
+        self.domains
+            .iter()
+            .map(|domain| read_counter(&domain.energy_path).unwrap_or(0))
+            .collect()
+    }
+}
+
+impl Measurement for RaplEnergy {
+    type Intermediate = Vec<u64>;
+    type Value = f64;
+
+    fn start(&self) -> Self::Intermediate {
+        self.read_all()
+    }
+
+    fn end(&self, start: Self::Intermediate) -> Self::Value {
+        let end = self.read_all();
+        self.domains
+            .iter()
+            .zip(start.iter().zip(&end))
+            .map(|(domain, (&start, &end))| counter_delta(start, end, domain.max_energy_uj) as f64)
+            .sum()
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0.0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &EnergyFormatter
+    }
+}
+
+/// Formats microjoules, and throughput as joules per unit of work.
+struct EnergyFormatter;
+
+impl ValueFormatter for EnergyFormatter {
+    fn scale_values(&self, typical_uj: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = if typical_uj < 1.0 {
+            (1e3, "nJ")
+        } else if typical_uj < 1e3 {
+            (1.0, "µJ")
+        } else if typical_uj < 1e6 {
+            (1e-3, "mJ")
+        } else {
+            (1e-6, "J")
+        };
+        for value in values {
+            *value *= factor;
+        }
+        unit
+    }
+
+    /// Energy per GiB, Gbit or Gelem, the same units as the `cargo xc` energy tables.
+    fn scale_throughputs(
+        &self,
+        _typical_uj: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (amount, scale, unit) = match *throughput {
+            Throughput::Bytes(bytes) | Throughput::BytesDecimal(bytes) => {
+                (bytes as f64, 1024f64.powi(3), "J/GiB")
+            }
+            Throughput::Bits(bits) => (bits as f64, 1e9, "J/Gbit"),
+            Throughput::Elements(elements) | Throughput::ElementsAndBytes { elements, .. } => {
+                (elements as f64, 1e9, "J/Gelem")
+            }
+        };
+        for value in values {
+            *value = *value * 1e-6 * scale / amount;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "uJ"
+    }
+}
+
+/// `intel-rapl:0` is a package, `intel-rapl:0:1` a sub-domain of it.
+fn is_package_domain(name: &str) -> bool {
+    name.strip_prefix("intel-rapl:")
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn read_counter(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn counter_delta(start: u64, end: u64, max: u64) -> u64 {
+    if end >= start {
+        end - start
+    } else {
+        max - start + end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use criterion::{Throughput, measurement::ValueFormatter};
+
+    use super::{EnergyFormatter, RaplEnergy, counter_delta, is_package_domain};
+
+    #[test]
+    fn package_domains_exclude_subdomains() {
+        assert!(is_package_domain("intel-rapl:0"));
+        assert!(is_package_domain("intel-rapl:12"));
+        assert!(!is_package_domain("intel-rapl:0:1"));
+        assert!(!is_package_domain("intel-rapl-mmio:0"));
+        assert!(!is_package_domain("intel-rapl"));
+    }
+
+    #[test]
+    fn counter_delta_handles_wraparound() {
+        assert_eq!(counter_delta(10, 25, 100), 15);
+        assert_eq!(counter_delta(90, 5, 100), 15);
+    }
+
+    #[test]
+    fn energy_throughput_is_joules_per_unit() {
+        let mut values = [1e6];
+        let unit = EnergyFormatter.scale_throughputs(1e6, &Throughput::Bytes(1 << 29), &mut values);
+        assert_eq!(unit, "J/GiB");
+        assert_eq!(values, [2.0]);
+        let mut values = [2.0];
+        let unit = EnergyFormatter.scale_throughputs(2.0, &Throughput::Elements(1000), &mut values);
+        assert_eq!(unit, "J/Gelem");
+        assert!((values[0] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn detect_reads_package_domains() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("bench-support-rapl-{nanos}"));
+        fs::create_dir_all(root.join("intel-rapl:0")).expect("create package dir");
+        fs::create_dir_all(root.join("intel-rapl:0:0")).expect("create subdomain dir");
+        fs::write(root.join("intel-rapl:0/energy_uj"), "100\n").expect("write counter");
+        fs::write(root.join("intel-rapl:0/max_energy_range_uj"), "1000\n").expect("write max");
+
+        let energy = RaplEnergy::detect_in(&root).expect("detect should succeed");
+        assert_eq!(energy.domains.len(), 1);
+        assert_eq!(energy.domains[0].max_energy_uj, 1000);
+        assert_eq!(energy.read_all(), vec![100]);
+
+        fs::remove_dir_all(&root).expect("cleanup");
+        assert!(RaplEnergy::detect_in(&root).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Shared helpers for the benchmark crates: alternative criterion measurements and the
+//! `main` that picks one at runtime.
+
+pub mod energy;
+pub mod perf;
+
+use std::{env, path::Path};
+
+use criterion::{Criterion, measurement::Measurement};
+
+use crate::{
+    energy::RaplEnergy,
+    perf::{PerfCounter, PerfEvent},
+};
+
+/// Environment variable selecting the measurement, see [`MeasurementKind`].
+pub const MEASUREMENT_ENV: &str = "BENCH_MEASUREMENT";
+
+/// What a benchmark iteration is measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeasurementKind {
+    /// Criterion's default wall-clock time.
+    WallTime,
+    /// Package energy from Linux RAPL counters, see [`RaplEnergy`].
+    Energy,
+    /// One hardware event from `perf_event_open`, see [`PerfCounter`].
+    Perf(PerfEvent),
+}
+
+impl MeasurementKind {
+    pub fn from_env() -> Result<Self, String> {
+        match env::var(MEASUREMENT_ENV) {
+            Ok(value) => Self::parse(&value),
+            Err(env::VarError::NotPresent) => Ok(Self::WallTime),
+            Err(error) => Err(format!("invalid {MEASUREMENT_ENV}: {error}")),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "" | "wall" => Ok(Self::WallTime),
+            "energy" => Ok(Self::Energy),
+            other => PerfEvent::ALL
+                .into_iter()
+                .find(|event| event.name() == other)
+                .map(Self::Perf)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown {MEASUREMENT_ENV} value `{other}`, expected `wall`, `energy`, \
+                         `cycles`, `instructions` or `cache-misses`"
+                    )
+                }),
+        }
+    }
+
+    /// Directory under the cargo target dir holding criterion output for this measurement.
+    ///
+    /// Must match `xtask`'s `criterion_dir`.
+    pub fn criterion_dir_name(self) -> &'static str {
+        match self {
+            Self::WallTime => "criterion",
+            Self::Energy => "criterion-energy",
+            Self::Perf(PerfEvent::Cycles) => "criterion-cycles",
+            Self::Perf(PerfEvent::Instructions) => "criterion-instructions",
+            Self::Perf(PerfEvent::CacheMisses) => "criterion-cache-misses",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::WallTime => "wall",
+            Self::Energy => "energy",
+            Self::Perf(event) => event.name(),
+        }
+    }
+}
+
+/// Benchmark targets that can run under any criterion measurement.
+pub trait BenchTargets {
+    fn run<M: Measurement>(&self, criterion: &mut Criterion<M>);
+}
+
+/// Replacement for `criterion_main!` that runs `targets` with the measurement selected by
+/// [`MEASUREMENT_ENV`].
+///
+/// Non-default measurements write to their own criterion directory (unless `CRITERION_HOME`
+/// is set), so they never overwrite wall-time results. Unavailable measurements are skipped
+/// with a warning instead of failing the run.
+pub fn bench_main(config: fn() -> Criterion, targets: &impl BenchTargets) {
+    let kind = match MeasurementKind::from_env() {
+        Ok(kind) => kind,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+    };
+
+    match kind {
+        MeasurementKind::WallTime => run(config(), targets),
+        MeasurementKind::Energy => run_or_skip(kind, RaplEnergy::detect(), config, targets),
+        MeasurementKind::Perf(event) => {
+            run_or_skip(kind, PerfCounter::open(event), config, targets)
+        }
+    }
+}
+
+fn run_or_skip<M: Measurement>(
+    kind: MeasurementKind,
+    measurement: Result<M, String>,
+    config: fn() -> Criterion,
+    targets: &impl BenchTargets,
+) {
+    match measurement {
+        Ok(measurement) => {
+            set_criterion_home(kind);
+            run(config().with_measurement(measurement), targets);
+        }
+        Err(reason) => eprintln!("skipping {} measurement: {reason}", kind.name()),
+    }
+}
+
+fn run<M: Measurement>(config: Criterion<M>, targets: &impl BenchTargets) {
+    let mut criterion = config.configure_from_args();
+    targets.run(&mut criterion);
+    criterion.final_summary();
+}
+
+fn set_criterion_home(kind: MeasurementKind) {
+    if env::var_os("CRITERION_HOME").is_some() {
+        return;
+    }
+    let target_dir = env::var_os("CARGO_TARGET_DIR")
+        .map(Into::into)
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .expect("workspace root should exist")
+                .join("target")
+        });
+    // SAFETY: called from `main` before any benchmark thread is spawned.
+    unsafe { env::set_var("CRITERION_HOME", target_dir.join(kind.criterion_dir_name())) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeasurementKind;
+    use crate::perf::PerfEvent;
+
+    #[test]
+    fn measurement_kind_parse() {
+        assert_eq!(MeasurementKind::parse(""), Ok(MeasurementKind::WallTime));
+        assert_eq!(
+            MeasurementKind::parse("wall"),
+            Ok(MeasurementKind::WallTime)
+        );
+        assert_eq!(
+            MeasurementKind::parse("energy"),
+            Ok(MeasurementKind::Energy)
+        );
+        assert_eq!(
+            MeasurementKind::parse("cache-misses"),
+            Ok(MeasurementKind::Perf(PerfEvent::CacheMisses))
+        );
+        assert!(MeasurementKind::parse("joules").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Hardware performance counters from Linux `perf_event_open`.
+
+use criterion::{
+    Throughput,
+    measurement::{Measurement, ValueFormatter},
+};
+
+/// Hardware event counted by [`PerfCounter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerfEvent {
+    Cycles,
+    Instructions,
+    CacheMisses,
+}
+
+impl PerfEvent {
+    pub const ALL: [PerfEvent; 3] = [
+        PerfEvent::Cycles,
+        PerfEvent::Instructions,
+        PerfEvent::CacheMisses,
+    ];
+
+    /// Name used in `BENCH_MEASUREMENT` and the criterion directory.
+    pub fn name(self) -> &'static str {
+        match self {
+            PerfEvent::Cycles => "cycles",
+            PerfEvent::Instructions => "instructions",
+            PerfEvent::CacheMisses => "cache-misses",
+        }
+    }
+
+    /// `PERF_COUNT_HW_*` config value.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn hw_config(self) -> u64 {
+        match self {
+            PerfEvent::Cycles => 0,
+            PerfEvent::Instructions => 1,
+            PerfEvent::CacheMisses => 3,
+        }
+    }
+}
+
+/// Criterion measurement counting one hardware event of the benchmark thread.
+///
+/// Only user-space events are counted, which works with the default
+/// `perf_event_paranoid = 2`. When the kernel multiplexes the counter, counts are
+/// scaled by `time_enabled / time_running` like `perf stat` does.
+#[derive(Debug)]
+pub struct PerfCounter {
+    event: PerfEvent,
+    #[cfg(target_os = "linux")]
+    counter: sys::Counter,
+}
+
+impl PerfCounter {
+    /// Open `event` for the calling thread, or explain why it cannot be counted.
+    #[cfg(target_os = "linux")]
+    pub fn open(event: PerfEvent) -> Result<Self, String> {
+        let counter = sys::Counter::open(sys::PERF_TYPE_HARDWARE, event.hw_config())
+            .map_err(|error| format!("cannot open {} counter: {error}", event.name()))?;
+        Ok(Self { event, counter })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn open(event: PerfEvent) -> Result<Self, String> {
+        Err(format!(
+            "cannot open {} counter: perf_event_open is only available on Linux",
+            event.name()
+        ))
+    }
+
+    fn read(&self) -> f64 {
+        #[cfg(target_os = "linux")]
+        {
+            self.counter.read_scaled().unwrap_or(0.0)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            0.0
+        }
+    }
+}
+
+impl Measurement for PerfCounter {
+    type Intermediate = f64;
+    type Value = f64;
+
+    fn start(&self) -> Self::Intermediate {
+        self.read()
+    }
+
+    fn end(&self, start: Self::Intermediate) -> Self::Value {
+        (self.read() - start).max(0.0)
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0.0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        match self.event {
+            PerfEvent::Cycles => &PerfFormatter(PerfEvent::Cycles),
+            PerfEvent::Instructions => &PerfFormatter(PerfEvent::Instructions),
+            PerfEvent::CacheMisses => &PerfFormatter(PerfEvent::CacheMisses),
+        }
+    }
+}
+
+/// Formats event counts, and throughput as events per byte / element / bit.
+struct PerfFormatter(PerfEvent);
+
+impl PerfFormatter {
+    fn unit(&self) -> &'static str {
+        match self.0 {
+            PerfEvent::Cycles => "cycles",
+            PerfEvent::Instructions => "instr",
+            PerfEvent::CacheMisses => "misses",
+        }
+    }
+}
+
+impl ValueFormatter for PerfFormatter {
+    fn scale_values(&self, typical: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = match (self.0, typical) {
+            (PerfEvent::Cycles, t) if t >= 1e9 => (1e-9, "Gcycles"),
+            (PerfEvent::Cycles, t) if t >= 1e6 => (1e-6, "Mcycles"),
+            (PerfEvent::Cycles, t) if t >= 1e3 => (1e-3, "Kcycles"),
+            (PerfEvent::Instructions, t) if t >= 1e9 => (1e-9, "Ginstr"),
+            (PerfEvent::Instructions, t) if t >= 1e6 => (1e-6, "Minstr"),
+            (PerfEvent::Instructions, t) if t >= 1e3 => (1e-3, "Kinstr"),
+            (PerfEvent::CacheMisses, t) if t >= 1e6 => (1e-6, "Mmisses"),
+            (PerfEvent::CacheMisses, t) if t >= 1e3 => (1e-3, "Kmisses"),
+            _ => (1.0, self.unit()),
+        };
+        for value in values {
+            *value *= factor;
+        }
+        unit
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (amount, per) = match *throughput {
+            Throughput::Bytes(bytes) | Throughput::BytesDecimal(bytes) => (bytes, "B"),
+            Throughput::Bits(bits) => (bits, "bit"),
+            Throughput::Elements(elements) | Throughput::ElementsAndBytes { elements, .. } => {
+                (elements, "elem")
+            }
+        };
+        for value in values {
+            *value /= amount as f64;
+        }
+        match (self.0, per) {
+            (PerfEvent::Cycles, "B") => "cycles/B",
+            (PerfEvent::Cycles, "bit") => "cycles/bit",
+            (PerfEvent::Cycles, _) => "cycles/elem",
+            (PerfEvent::Instructions, "B") => "instr/B",
+            (PerfEvent::Instructions, "bit") => "instr/bit",
+            (PerfEvent::Instructions, _) => "instr/elem",
+            (PerfEvent::CacheMisses, "B") => "misses/B",
+            (PerfEvent::CacheMisses, "bit") => "misses/bit",
+            (PerfEvent::CacheMisses, _) => "misses/elem",
+        }
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        self.unit()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::{
+        fs::File,
+        io::{self, Read},
+        os::fd::FromRawFd,
+    };
+
+    pub const PERF_TYPE_HARDWARE: u32 = 0;
+    #[cfg(test)]
+    pub const PERF_TYPE_SOFTWARE: u32 = 1;
+
+    const PERF_FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+    const PERF_FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    /// `struct perf_event_attr` up to `config1` (`PERF_ATTR_SIZE_VER0`); the kernel
+    /// zero-extends older layouts.
+    #[repr(C)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    #[derive(Debug)]
+    pub struct Counter {
+        file: File,
+    }
+
+    impl Counter {
+        pub fn open(type_: u32, config: u64) -> io::Result<Self> {
+            let attr = PerfEventAttr {
+                type_,
+                size: size_of::<PerfEventAttr>() as u32,
+                config,
+                sample_period: 0,
+                sample_type: 0,
+                read_format: PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING,
+                flags: EXCLUDE_KERNEL | EXCLUDE_HV,
+                wakeup_events: 0,
+                bp_type: 0,
+                config1: 0,
+            };
+            // SAFETY: `attr` is a valid `perf_event_attr` of the declared size; pid 0 and
+            // cpu -1 count the calling thread on any CPU.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0 as libc::pid_t,
+                    -1 as libc::c_int,
+                    -1 as libc::c_int,
+                    PERF_FLAG_FD_CLOEXEC,
+                )
+            };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: the syscall returned a new file descriptor owned by nobody else.
+            let file = unsafe { File::from_raw_fd(fd as libc::c_int) };
+            Ok(Self { file })
+        }
+
+        /// Current count, scaled up for the time the counter was multiplexed out.
+        pub fn read_scaled(&self) -> io::Result<f64> {
+            let mut buf = [0u8; 24];
+            (&self.file).read_exact(&mut buf)?;
+            let word = |i: usize| {
+                u64::from_ne_bytes(buf[i * 8..i * 8 + 8].try_into().expect("8-byte word"))
+            };
+            let (value, enabled, running) = (word(0), word(1), word(2));
+            if running == 0 {
+                return Ok(0.0);
+            }
+            Ok(value as f64 * enabled as f64 / running as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use criterion::{Throughput, measurement::ValueFormatter};
+
+    use super::{PerfEvent, PerfFormatter};
+
+    #[test]
+    fn throughput_is_events_per_unit() {
+        let mut values = [4096.0];
+        let unit = PerfFormatter(PerfEvent::Cycles).scale_throughputs(
+            4096.0,
+            &Throughput::Bytes(1024),
+            &mut values,
+        );
+        assert_eq!(unit, "cycles/B");
+        assert_eq!(values, [4.0]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn software_counter_reads() {
+        // PERF_COUNT_SW_TASK_CLOCK exists without a hardware PMU, so this checks the
+        // syscall plumbing on virtual machines too. Skipped where perf is disabled.
+        const PERF_COUNT_SW_TASK_CLOCK: u64 = 1;
+        let Ok(counter) =
+            super::sys::Counter::open(super::sys::PERF_TYPE_SOFTWARE, PERF_COUNT_SW_TASK_CLOCK)
+        else {
+            return;
+        };
+        let start = counter.read_scaled().expect("read counter");
+        std::hint::black_box((0..100_000u64).sum::<u64>());
+        let end = counter.read_scaled().expect("read counter");
+        assert!(end > start);
+    }
+}
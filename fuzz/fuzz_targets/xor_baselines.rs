@@ -0,0 +1,70 @@
+//! Compare the `unsafe` XOR baselines with their safe references.
+//!
+//! The first input byte selects the start offset (`0..64`) from a 64-byte aligned address,
+//! the rest is the data to hash.
+
+#![no_main]
+
+use std::{
+    alloc::{Layout, alloc_zeroed, dealloc},
+    slice,
+};
+
+use bench_hash::{xor_hash64, xor_hash64_reference, xor_hash128_reference, xor_hash128_variants};
+use libfuzzer_sys::fuzz_target;
+
+/// Heap copy of the data starting `offset` bytes past a 64-byte aligned address and ending
+/// exactly at the end of its allocation, so any over-read trips ASan.
+struct Placed {
+    ptr: *mut u8,
+    layout: Layout,
+    offset: usize,
+    len: usize,
+}
+
+impl Placed {
+    fn new(data: &[u8], offset: usize) -> Self {
+        let layout =
+            Layout::from_size_align((offset + data.len()).max(1), 64).expect("valid layout");
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { alloc_zeroed(layout) };
+        assert!(!ptr.is_null(), "allocation failed");
+        // SAFETY: the allocation holds `offset + data.len()` bytes.
+        unsafe {
+            ptr.add(offset)
+                .copy_from_nonoverlapping(data.as_ptr(), data.len())
+        };
+        Self {
+            ptr,
+            layout,
+            offset,
+            len: data.len(),
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        // SAFETY: initialized in `new`, in bounds of the allocation.
+        unsafe { slice::from_raw_parts(self.ptr.add(self.offset), self.len) }
+    }
+}
+
+impl Drop for Placed {
+    fn drop(&mut self) {
+        // SAFETY: allocated in `new` with the same layout.
+        unsafe { dealloc(self.ptr, self.layout) };
+    }
+}
+
+fuzz_target!(|input: &[u8]| {
+    let Some((&offset, data)) = input.split_first() else {
+        return;
+    };
+    let placed = Placed::new(data, usize::from(offset % 64));
+    let data = placed.as_slice();
+
+    assert_eq!(xor_hash64(data), xor_hash64_reference(data), "xor_hash64");
+    let expected = xor_hash128_reference(data);
+    for (name, f) in xor_hash128_variants() {
+        assert_eq!(f(data), expected, "{name}");
+    }
+});
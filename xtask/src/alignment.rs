@@ -0,0 +1,136 @@
+use std::{io::Write, path::Path};
+
+use crate::{
+    criterion::{BenchmarkRecord, read_group},
+    util::{Result, format_bytes_rate},
+};
+
+/// Criterion group of the hash benchmarks run at offsets `0..=63` from an aligned address.
+pub const UNALIGNED_GROUP: &str = "unaligned_hash";
+
+/// Slowdown of one hasher on unaligned input, relative to offset `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignmentPenalty {
+    pub function: String,
+    pub aligned_rate: f64,
+    /// Mean slowdown over all non-zero offsets (`0.05` = 5% slower).
+    pub average: f64,
+    pub worst: f64,
+    pub worst_offset: String,
+}
+
+pub fn alignment_penalties(records: &[BenchmarkRecord]) -> Vec<AlignmentPenalty> {
+    let mut penalties = Vec::new();
+    let mut functions: Vec<&str> = records.iter().map(|r| r.function.as_str()).collect();
+    functions.dedup();
+
+    for function in functions {
+        let cases: Vec<&BenchmarkRecord> =
+            records.iter().filter(|r| r.function == function).collect();
+        let Some(aligned) = cases.iter().find(|r| r.parameter.as_deref() == Some("0")) else {
+            continue;
+        };
+        let Some(aligned_rate) = aligned.throughput_per_sec() else {
+            continue;
+        };
+
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        let mut worst: Option<(f64, &str)> = None;
+        for case in &cases {
+            let Some(offset) = case.parameter.as_deref().filter(|offset| *offset != "0") else {
+                continue;
+            };
+            let penalty = case.mean.point / aligned.mean.point - 1.0;
+            sum += penalty;
+            count += 1;
+            if worst.is_none_or(|(w, _)| penalty > w) {
+                worst = Some((penalty, offset));
+            }
+        }
+        let Some((worst, worst_offset)) = worst else {
+            continue;
+        };
+
+        penalties.push(AlignmentPenalty {
+            function: function.to_owned(),
+            aligned_rate,
+            average: sum / count as f64,
+            worst,
+            worst_offset: worst_offset.to_owned(),
+        });
+    }
+
+    penalties
+}
+
+/// Write the penalty table when unaligned results exist in `criterion_dir`.
+pub fn write_penalty_markdown(writer: &mut impl Write, criterion_dir: &Path) -> Result<()> {
+    let records = read_group(criterion_dir, UNALIGNED_GROUP)?;
+    let penalties = alignment_penalties(&records);
+    if penalties.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "#### Unaligned Input Penalty")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Slowdown when the input starts at offsets 1..=63 from a 64-byte aligned address."
+    )?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "| Hasher | Aligned | Average penalty | Worst penalty | Worst offset |"
+    )?;
+    writeln!(writer, "| --- | --- | --- | --- | --- |")?;
+    for penalty in penalties {
+        writeln!(
+            writer,
+            "| `{}` | {} | {:+.1}% | {:+.1}% | {} |",
+            penalty.function,
+            format_bytes_rate(penalty.aligned_rate),
+            penalty.average * 100.0,
+            penalty.worst * 100.0,
+            penalty.worst_offset
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alignment_penalties;
+    use crate::criterion::{BenchmarkRecord, Throughput, tests::record};
+
+    /// A 4 KiB input hashed at `offset` bytes past an aligned address.
+    fn at_offset(function: &str, offset: usize, mean: f64) -> BenchmarkRecord {
+        BenchmarkRecord {
+            parameter: Some(offset.to_string()),
+            ..record(function, Throughput::Bytes(4096), mean)
+        }
+    }
+
+    #[test]
+    fn penalties_relative_to_offset_zero() {
+        let records = vec![
+            at_offset("XXH3-64", 0, 100.0),
+            at_offset("XXH3-64", 1, 110.0),
+            at_offset("XXH3-64", 2, 130.0),
+            at_offset("XXH3-64", 3, 90.0),
+        ];
+        let penalties = alignment_penalties(&records);
+        assert_eq!(penalties.len(), 1);
+        let penalty = &penalties[0];
+        assert!((penalty.average - 0.1).abs() < 1e-9);
+        assert!((penalty.worst - 0.3).abs() < 1e-9);
+        assert_eq!(penalty.worst_offset, "2");
+    }
+
+    #[test]
+    fn penalties_skip_functions_without_aligned_case() {
+        let records = vec![at_offset("XOR-64-ILP", 1, 10.0)];
+        assert!(alignment_penalties(&records).is_empty());
+    }
+}
@@ -1,40 +1,103 @@
 use std::{io, path::Path, process::Command};
 
 use crate::{
+    build_config::BuildConfig,
     cli::{BenchOpts, RunOpts},
+    criterion::{criterion_dir, output_stamp},
+    placement::Placement,
+    preflight::PreflightReport,
     scope::Scope,
+    toolchains::{self, Toolchain},
     util::{Result, workspace_root},
+    versions::LibraryVersions,
 };
 
 pub fn run_benchmarks(args: &RunOpts) -> Result<()> {
     let workspace_root = workspace_root();
+    let preflight = PreflightReport::detect();
+    preflight.enforce(args.bench_args.strict)?;
+    let placement = Placement::from_opts(&args.bench_args)?;
+    // Toolchain runs leave `target/criterion`, and what describes it, alone.
+    if !args.toolchain.is_empty() {
+        return toolchains::run_toolchains(args, &placement, workspace_root);
+    }
+    let build = BuildConfig::detect(workspace_root, None);
+    let versions = LibraryVersions::resolve(workspace_root)
+        .inspect_err(|error| eprintln!("warning: cannot resolve library versions: {error}"))
+        .ok();
+    let criterion_dir = criterion_dir(workspace_root);
+    let before = output_stamp(&criterion_dir);
     for &scope in args.scope.to_scopes() {
-        run_benchmark(scope, &args.bench_args, workspace_root)?;
+        run_benchmark(scope, &args.bench_args, &placement, workspace_root, None)?;
+    }
+    // Saved for the output just written, so `collect` never pairs it with other results;
+    // runs of other measurements only write next to `target/criterion`.
+    let Some(stamp) = output_stamp(&criterion_dir).filter(|stamp| Some(stamp) != before.as_ref())
+    else {
+        return Ok(());
+    };
+    preflight.save_last(workspace_root, &stamp)?;
+    placement.save_last(workspace_root, &stamp)?;
+    build.save_last(workspace_root, &stamp)?;
+    if let Some(versions) = versions {
+        versions.save_last(workspace_root, &stamp)?;
     }
     Ok(())
 }
 
-pub fn run_benchmark(scope: Scope, bench_args: &BenchOpts, workspace_root: &Path) -> Result<()> {
-    let mut cmd = Command::new("cargo");
-    cmd.current_dir(workspace_root)
-        .arg("bench")
-        .args(["-p", scope.bench_crate()])
-        .args(["--bench", scope.bench_file()])
-        .arg("--")
-        .args(["--plotting-backend", "plotters"]);
-
-    if bench_args.quick {
-        cmd.args(["--warm-up-time", "1"]);
-        cmd.args(["--measurement-time", "1"]);
+/// Run the benchmarks of `scope`, with `toolchain` and into its own target directory when
+/// given.
+pub fn run_benchmark(
+    scope: Scope,
+    bench_args: &BenchOpts,
+    placement: &Placement,
+    workspace_root: &Path,
+    toolchain: Option<&Toolchain>,
+) -> Result<()> {
+    let cargo = || {
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(workspace_root);
+        if let Some(toolchain) = toolchain {
+            cmd.arg(format!("+{}", toolchain.name))
+                .env("CARGO_TARGET_DIR", toolchain.target_dir(workspace_root));
+        }
+        cmd
+    };
+    if !placement.is_default() {
+        // Build unpinned so compilation does not compete for the benchmark CPUs.
+        let status = cargo()
+            .args(["bench", "--no-run"])
+            .args(["-p", scope.bench_crate()])
+            .args(["--bench", scope.bench_file()])
+            .status()?;
+        if !status.success() {
+            return Err(format!("benchmark build failed for scope: {scope}").into());
+        }
     }
 
-    cmd.args(&bench_args.bench_extra_args);
+    for measurement in bench_args.measurement.env_values() {
+        let mut cmd = cargo();
+        cmd.arg("bench")
+            .args(["-p", scope.bench_crate()])
+            .args(["--bench", scope.bench_file()])
+            .env("BENCH_MEASUREMENT", measurement)
+            .arg("--")
+            .args(["--plotting-backend", "plotters"]);
 
-    eprintln!("Running `{:?}`", cmd);
+        if bench_args.quick {
+            cmd.args(["--warm-up-time", "1"]);
+            cmd.args(["--measurement-time", "1"]);
+        }
+
+        cmd.args(&bench_args.bench_extra_args);
+        placement.apply(&mut cmd)?;
 
-    let status = cmd.status()?;
-    if !status.success() {
-        return Err(format!("benchmark command failed for scope: {scope}").into());
+        eprintln!("Running `{:?}`", cmd);
+
+        let status = cmd.status()?;
+        if !status.success() {
+            return Err(format!("benchmark command failed for scope: {scope}").into());
+        }
     }
     Ok(())
 }
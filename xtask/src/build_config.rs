@@ -0,0 +1,257 @@
+use std::{env, fs, io::Write, path::Path};
+
+use crate::{
+    metadata::Ini,
+    util::{Result, load_last, run_capture, save_last},
+};
+
+/// Where `xtask run` stores the build configuration of the last run, for `collect`.
+const LAST_BUILD_FILE: &str = "target/xtask/build.ini";
+
+/// How the benchmark binaries were compiled.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildConfig {
+    /// Effective rustflags, from `RUSTFLAGS` or `.cargo/config.toml`.
+    pub rustflags: Vec<String>,
+    pub target: Option<String>,
+    /// `target_feature` cfgs enabled by `rustflags`, as reported by `rustc --print cfg`.
+    pub target_features: Vec<String>,
+    /// `[profile.bench]` settings of the workspace manifest, e.g. `lto = "fat"`.
+    pub profile: Vec<(String, String)>,
+}
+
+impl BuildConfig {
+    /// Detect the configuration `cargo bench` builds with, under the rustup `toolchain` of
+    /// `cargo +{toolchain}` or, for `None`, the `rustc` on `PATH`.
+    pub fn detect(workspace_root: &Path, toolchain: Option<&str>) -> Self {
+        let rustc = |args: &[&str]| match toolchain {
+            Some(toolchain) => {
+                let mut rustup_args = vec!["run", toolchain, "rustc"];
+                rustup_args.extend_from_slice(args);
+                run_capture("rustup", &rustup_args)
+            }
+            None => run_capture("rustc", args),
+        };
+        let target = rustc(&["-vV"]).and_then(|out| {
+            out.lines()
+                .find_map(|line| line.strip_prefix("host:"))
+                .map(|host| host.trim().to_owned())
+        });
+        let rustflags = effective_rustflags(workspace_root, target.as_deref().unwrap_or(""));
+        let mut args = vec!["--print", "cfg"];
+        args.extend(rustflags.iter().map(String::as_str));
+        let target_features = rustc(&args)
+            .map(|out| parse_target_features(&out))
+            .unwrap_or_default();
+        let profile = fs::read_to_string(workspace_root.join("Cargo.toml"))
+            .ok()
+            .and_then(|manifest| parse_bench_profile(&manifest))
+            .unwrap_or_default();
+
+        Self {
+            rustflags,
+            target,
+            target_features,
+            profile,
+        }
+    }
+
+    /// `[build]` section of `environment.ini`.
+    pub fn encode_ini(&self) -> String {
+        let mut out = String::from("[build]\n");
+        out.push_str(&format!("rustflags = {}\n", self.rustflags.join(" ")));
+        if let Some(target) = &self.target {
+            out.push_str(&format!("target = {target}\n"));
+        }
+        out.push_str(&format!(
+            "target_features = {}\n",
+            self.target_features.join(",")
+        ));
+        for (key, value) in &self.profile {
+            out.push_str(&format!("profile.bench.{key} = {value}\n"));
+        }
+        out
+    }
+
+    /// Decode the `[build]` section written by [`Self::encode_ini`].
+    pub fn decode_section(entries: &[(String, String)]) -> std::result::Result<Self, String> {
+        let mut config = Self::default();
+        for (key, value) in entries {
+            match key.as_str() {
+                "rustflags" => {
+                    config.rustflags = value.split_whitespace().map(str::to_owned).collect();
+                }
+                "target" => config.target = Some(value.to_owned()),
+                "target_features" => {
+                    config.target_features = value
+                        .split(',')
+                        .filter(|feature| !feature.is_empty())
+                        .map(str::to_owned)
+                        .collect();
+                }
+                key => match key.strip_prefix("profile.bench.") {
+                    Some(key) => config.profile.push((key.to_owned(), value.to_owned())),
+                    None => return Err(format!("unknown key `{key}` in [build]")),
+                },
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn write_markdown(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writeln!(writer, "### Build Configuration")?;
+        writeln!(writer)?;
+        let rustflags = if self.rustflags.is_empty() {
+            "(none)".to_owned()
+        } else {
+            format!("`{}`", self.rustflags.join(" "))
+        };
+        writeln!(writer, "- RUSTFLAGS: {rustflags}")?;
+        if let Some(target) = &self.target {
+            writeln!(writer, "- Target: `{target}`")?;
+        }
+        if !self.profile.is_empty() {
+            let profile: Vec<String> = self
+                .profile
+                .iter()
+                .map(|(key, value)| format!("{key} = {value}"))
+                .collect();
+            writeln!(writer, "- Bench profile: `{}`", profile.join(", "))?;
+        }
+        if !self.target_features.is_empty() {
+            writeln!(
+                writer,
+                "- Target features: {}",
+                self.target_features.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn save_last(&self, workspace_root: &Path, stamp: &str) -> Result<()> {
+        save_last(workspace_root, LAST_BUILD_FILE, stamp, &self.encode_ini())
+    }
+
+    /// Build configuration of the `xtask run` that wrote the criterion output `stamp`, if any.
+    pub fn load_last(workspace_root: &Path, stamp: Option<&str>) -> Option<Self> {
+        let raw = load_last(workspace_root, LAST_BUILD_FILE, stamp)?;
+        Self::decode_ini(&raw).ok()
+    }
+
+    fn decode_ini(input: &str) -> std::result::Result<Self, String> {
+        Self::decode_section(Ini::parse(input)?.entries("build"))
+    }
+}
+
+/// Rustflags cargo uses for the benchmarks, following cargo's precedence: the
+/// environment, then `target.<triple>.rustflags`, then `build.rustflags`.
+fn effective_rustflags(workspace_root: &Path, host: &str) -> Vec<String> {
+    if let Ok(encoded) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        return encoded
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(str::to_owned)
+            .collect();
+    }
+    if let Ok(flags) = env::var("RUSTFLAGS") {
+        return flags.split_whitespace().map(str::to_owned).collect();
+    }
+    let Ok(raw) = fs::read_to_string(workspace_root.join(".cargo/config.toml")) else {
+        return Vec::new();
+    };
+    config_rustflags(&raw, host).unwrap_or_default()
+}
+
+fn config_rustflags(config: &str, host: &str) -> Option<Vec<String>> {
+    let config: toml::Table = config.parse().ok()?;
+    let flags = config
+        .get("target")
+        .and_then(|target| target.get(host))
+        .and_then(|target| target.get("rustflags"))
+        .or_else(|| config.get("build")?.get("rustflags"))?;
+    match flags {
+        toml::Value::String(flags) => Some(flags.split_whitespace().map(str::to_owned).collect()),
+        toml::Value::Array(flags) => Some(
+            flags
+                .iter()
+                .filter_map(|flag| flag.as_str().map(str::to_owned))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+fn parse_target_features(cfg: &str) -> Vec<String> {
+    cfg.lines()
+        .filter_map(|line| line.strip_prefix("target_feature=\""))
+        .filter_map(|feature| feature.strip_suffix('"'))
+        .map(str::to_owned)
+        .collect()
+}
+
+fn parse_bench_profile(manifest: &str) -> Option<Vec<(String, String)>> {
+    let manifest: toml::Table = manifest.parse().ok()?;
+    let profile = manifest.get("profile")?.get("bench")?.as_table()?;
+    Some(
+        profile
+            .iter()
+            .map(|(key, value)| (key.clone(), value.to_string()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildConfig, config_rustflags, parse_bench_profile, parse_target_features};
+
+    #[test]
+    fn config_rustflags_prefers_target_table() {
+        let config = r#"
+[build]
+rustflags = ["-Ctarget-cpu=native"]
+
+[target.aarch64-apple-darwin]
+rustflags = "-Ctarget-cpu=apple-m1 -Copt-level=3"
+"#;
+        assert_eq!(
+            config_rustflags(config, "x86_64-unknown-linux-gnu"),
+            Some(vec!["-Ctarget-cpu=native".to_owned()])
+        );
+        assert_eq!(
+            config_rustflags(config, "aarch64-apple-darwin"),
+            Some(vec![
+                "-Ctarget-cpu=apple-m1".to_owned(),
+                "-Copt-level=3".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn target_features_and_profile() {
+        let cfg = "panic=\"unwind\"\ntarget_feature=\"aes\"\ntarget_feature=\"avx2\"\n";
+        assert_eq!(parse_target_features(cfg), ["aes", "avx2"]);
+
+        let manifest = "[profile.bench]\nlto = \"fat\"\ncodegen-units = 1\n";
+        assert_eq!(
+            parse_bench_profile(manifest),
+            Some(vec![
+                ("codegen-units".to_owned(), "1".to_owned()),
+                ("lto".to_owned(), "\"fat\"".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn ini_roundtrip() {
+        let config = BuildConfig {
+            rustflags: vec!["-Ctarget-cpu=native".to_owned()],
+            target: Some("x86_64-unknown-linux-gnu".to_owned()),
+            target_features: vec!["aes".to_owned(), "avx2".to_owned()],
+            profile: vec![("lto".to_owned(), "\"fat\"".to_owned())],
+        };
+        assert_eq!(
+            BuildConfig::decode_ini(&config.encode_ini()).expect("decode"),
+            config
+        );
+    }
+}
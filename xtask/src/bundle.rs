@@ -0,0 +1,466 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    cli::{BundleCommand, BundleCreateOpts, BundleOpts},
+    criterion::{criterion_dir, read_group},
+    environment::{BenchmarkEnvironment, read_metadata_ini},
+    import::extract_archive,
+    runs::{RUNS_DIR, SummaryEntry, latest_run_dir, new_run_id, read_summary, run_timestamp},
+    samples::{copy_samples, extract_samples},
+    util::{Result, run_capture_checked, workspace_root},
+};
+
+/// Identifies a bundle manifest.
+const FORMAT: &str = "bench-results-bundle";
+/// Version of the bundle layout this `xtask` writes; older ones stay readable.
+pub const SCHEMA_VERSION: u32 = 1;
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Copy of the run directory inside a bundle.
+pub const RUN_DIR: &str = "run";
+/// Criterion case directories of the run's summary groups inside a bundle.
+const SAMPLES_DIR: &str = "criterion";
+const ARCHIVE_EXTENSION: &str = ".tar.zst";
+/// Where archives are unpacked for verification.
+const EXTRACT_DIR: &str = "target/xtask/bundle";
+
+/// `manifest.json` at the root of a bundle.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub schema_version: u32,
+    /// Directory under `results/` the run was collected into.
+    pub host: String,
+    pub run: String,
+    /// Bundle creation time, RFC 3339 in UTC.
+    pub created: String,
+    /// Machine sections of `environment.ini`: `environment`, `hardware`, `dependencies.*`.
+    pub environment: Vec<Section>,
+    /// How the benchmarks were built and run: the `build`, `placement` and `preflight`
+    /// sections of `environment.ini`.
+    pub settings: Vec<Section>,
+    /// Whether `criterion/` holds the raw samples behind `estimates`.
+    pub raw_samples: bool,
+    pub estimates: Vec<SummaryEntry>,
+    /// Every other file of the bundle, sorted by path.
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Section {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// Relative to the bundle root, `/`-separated.
+    pub path: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+pub fn run_bundle(opts: &BundleOpts) -> Result<()> {
+    let workspace_root = workspace_root();
+    match &opts.command {
+        BundleCommand::Create(args) => {
+            let out = create_bundle(args, workspace_root)?;
+            println!("{}", out.display());
+        }
+        BundleCommand::Verify(args) => {
+            let manifest = if args.path.is_file() {
+                let extract_dir = workspace_root.join(EXTRACT_DIR);
+                extract_archive(&args.path, &extract_dir)?;
+                let manifest = verify_bundle(&extract_dir);
+                fs::remove_dir_all(&extract_dir)?;
+                manifest?
+            } else {
+                verify_bundle(&args.path)?
+            };
+            println!(
+                "{}: bundle v{} of {} run {}, {} files, {} estimates{}: OK",
+                args.path.display(),
+                manifest.schema_version,
+                manifest.host,
+                manifest.run,
+                manifest.files.len(),
+                manifest.estimates.len(),
+                if manifest.raw_samples {
+                    " with raw samples"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+    Ok(())
+}
+
+fn create_bundle(opts: &BundleCreateOpts, workspace_root: &Path) -> Result<PathBuf> {
+    let host = opts
+        .host
+        .clone()
+        .unwrap_or_else(|| BenchmarkEnvironment::current_result_name(&opts.current_host));
+    let host_dir = workspace_root.join("results").join(&host);
+    let run_dir = match &opts.run {
+        Some(run) => host_dir.join(RUNS_DIR).join(run),
+        None => latest_run_dir(&host_dir),
+    };
+    let run = run_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| run_timestamp(name).is_some())
+        .ok_or_else(|| format!("no collected run of `{host}` in {}", host_dir.display()))?;
+    if !run_dir.is_dir() {
+        return Err(format!("{} does not exist", run_dir.display()).into());
+    }
+
+    let out = opts.out.clone().unwrap_or_else(|| {
+        workspace_root
+            .join("target/bundles")
+            .join(format!("{host}-{run}{ARCHIVE_EXTENSION}"))
+    });
+    let archive = out.to_string_lossy().ends_with(ARCHIVE_EXTENSION);
+    let bundle_dir = if archive {
+        PathBuf::from(format!("{}.tmp", out.display()))
+    } else {
+        out.clone()
+    };
+    if bundle_dir.exists() {
+        fs::remove_dir_all(&bundle_dir)?;
+    }
+    write_bundle_dir(
+        &bundle_dir,
+        &host,
+        &run,
+        &run_dir,
+        &criterion_dir(workspace_root),
+    )?;
+
+    if archive {
+        if out.exists() {
+            fs::remove_file(&out)?;
+        }
+        run_capture_checked("tar", &[
+            "--zstd",
+            "-cf",
+            &out.to_string_lossy(),
+            "-C",
+            &bundle_dir.to_string_lossy(),
+            ".",
+        ])
+        .map_err(|error| format!("cannot write {}: {error}", out.display()))?;
+        fs::remove_dir_all(&bundle_dir)?;
+    }
+    Ok(out)
+}
+
+/// Write the bundle of `run_dir` into `bundle_dir`, with the raw samples of
+/// `criterion_dir` if they are still the run's.
+pub fn write_bundle_dir(
+    bundle_dir: &Path,
+    host: &str,
+    run: &str,
+    run_dir: &Path,
+    criterion_dir: &Path,
+) -> Result<()> {
+    let estimates = read_summary(run_dir)?.ok_or_else(|| {
+        format!(
+            "{} has no summary.json; collect the run again to bundle it",
+            run_dir.display()
+        )
+    })?;
+    let metadata = read_metadata_ini(run_dir)
+        .ok_or_else(|| format!("{} has no environment.ini", run_dir.display()))?;
+
+    copy_tree(run_dir, &bundle_dir.join(RUN_DIR))?;
+    let samples_dir = bundle_dir.join(SAMPLES_DIR);
+    let raw_samples = if samples_match(criterion_dir, &estimates)? {
+        let mut groups: Vec<&str> = estimates.iter().map(|entry| entry.group.as_str()).collect();
+        groups.dedup();
+        copy_samples(criterion_dir, &samples_dir, &groups)?;
+        true
+    } else if extract_samples(run_dir, &samples_dir)? && samples_match(&samples_dir, &estimates)? {
+        true
+    } else {
+        if samples_dir.exists() {
+            fs::remove_dir_all(&samples_dir)?;
+        }
+        eprintln!(
+            "warning: neither {} nor the run hold the measurements of run {run}; bundling it \
+             without raw samples",
+            criterion_dir.display()
+        );
+        false
+    };
+
+    let (settings, environment) = metadata
+        .sections
+        .into_iter()
+        .map(|(name, entries)| Section { name, entries })
+        .partition(|section| matches!(section.name.as_str(), "build" | "placement" | "preflight"));
+    let manifest = Manifest {
+        format: FORMAT.to_owned(),
+        schema_version: SCHEMA_VERSION,
+        host: host.to_owned(),
+        run: run.to_owned(),
+        created: run_timestamp(&new_run_id()).unwrap_or_default(),
+        environment,
+        settings,
+        raw_samples,
+        estimates,
+        files: checksum_files(bundle_dir)?,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|error| format!("failed to encode bundle manifest: {error}"))?;
+    fs::write(bundle_dir.join(MANIFEST_FILE), json)?;
+    Ok(())
+}
+
+/// Whether the estimates criterion computes from `criterion_dir` are the run's.
+fn samples_match(criterion_dir: &Path, estimates: &[SummaryEntry]) -> Result<bool> {
+    let mut groups: Vec<&str> = estimates.iter().map(|entry| entry.group.as_str()).collect();
+    groups.dedup();
+    let mut recorded = Vec::new();
+    for group in groups {
+        recorded.extend(read_group(criterion_dir, group)?);
+    }
+    let expected = estimates.iter().map(|entry| &entry.record);
+    Ok(!recorded.is_empty()
+        && recorded.len() == estimates.len()
+        && recorded
+            .iter()
+            .zip(expected)
+            .all(|(recorded, expected)| same_json(recorded, expected)))
+}
+
+/// Check a bundle directory against its manifest and return the manifest.
+pub fn verify_bundle(bundle_dir: &Path) -> Result<Manifest> {
+    let path = bundle_dir.join(MANIFEST_FILE);
+    let raw = fs::read_to_string(&path)
+        .map_err(|error| format!("cannot read {}: {error}", path.display()))?;
+    let manifest: Manifest = serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {error}", path.display()))?;
+    if manifest.format != FORMAT {
+        return Err(format!("{} is not a bundle manifest", path.display()).into());
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(format!(
+            "bundle schema version {} is newer than the supported {SCHEMA_VERSION}; update xtask",
+            manifest.schema_version
+        )
+        .into());
+    }
+
+    let files = checksum_files(bundle_dir)?;
+    for expected in &manifest.files {
+        match files.iter().find(|file| file.path == expected.path) {
+            None => return Err(format!("bundle is missing {}", expected.path).into()),
+            Some(file) if file != expected => {
+                return Err(format!("checksum mismatch for {}", expected.path).into());
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(extra) = files
+        .iter()
+        .find(|file| !manifest.files.iter().any(|listed| listed.path == file.path))
+    {
+        return Err(format!("{} is not listed in the manifest", extra.path).into());
+    }
+
+    let run_dir = bundle_dir.join(RUN_DIR);
+    let summary =
+        read_summary(&run_dir)?.ok_or_else(|| format!("bundle has no {RUN_DIR}/summary.json"))?;
+    if !same_json(&summary, &manifest.estimates) {
+        return Err("manifest estimates differ from the run summary".into());
+    }
+    if manifest.raw_samples && !samples_match(&bundle_dir.join(SAMPLES_DIR), &manifest.estimates)? {
+        return Err("raw samples do not reproduce the manifest estimates".into());
+    }
+    Ok(manifest)
+}
+
+/// Size and SHA-256 of every file under `root` except the manifest, sorted by path.
+fn checksum_files(root: &Path) -> Result<Vec<FileEntry>> {
+    let mut paths = Vec::new();
+    list_files(root, &mut paths)?;
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let relative = path
+            .strip_prefix(root)?
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if relative == MANIFEST_FILE {
+            continue;
+        }
+        let bytes = fs::read(&path)?;
+        files.push(FileEntry {
+            path: relative,
+            bytes: bytes.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn list_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_files(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    let mut paths = Vec::new();
+    list_files(from, &mut paths)?;
+    for path in paths {
+        let target = to.join(path.strip_prefix(from)?);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&path, target)?;
+    }
+    Ok(())
+}
+
+fn same_json<T: Serialize>(a: &T, b: &T) -> bool {
+    matches!(
+        (serde_json::to_value(a), serde_json::to_value(b)),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{MANIFEST_FILE, SCHEMA_VERSION, verify_bundle, write_bundle_dir};
+    use crate::{criterion::tests::write_case, runs::write_summary};
+
+    #[test]
+    fn bundle_roundtrip_and_tampering() {
+        let root = temp_dir("bundle");
+        let criterion = root.join("criterion");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            50.0,
+        );
+        write_case(
+            &criterion,
+            "cryptographic_hash",
+            "BLAKE3-256",
+            "1024",
+            1024,
+            400.0,
+        );
+        fs::write(
+            criterion.join("non_cryptographic_hash/XXH3-64/1024/new/sample.json"),
+            r#"{"iters":[1.0,2.0],"times":[50.0,100.0]}"#,
+        )
+        .expect("write sample");
+        let run_dir = root.join("results/apple-m4-macos/runs/2026-03-01T08-44-54Z");
+        fs::create_dir_all(run_dir.join("charts")).expect("create run");
+        fs::write(run_dir.join("charts/a.svg"), "<svg/>").expect("write chart");
+        fs::write(
+            run_dir.join("environment.ini"),
+            "[environment]\ncpu = Apple M4\n\n[build]\nrustflags = -Ctarget-cpu=native\n",
+        )
+        .expect("write meta");
+        write_summary(&run_dir, &criterion).expect("write summary");
+
+        let bundle = root.join("bundle");
+        write_bundle_dir(
+            &bundle,
+            "apple-m4-macos",
+            "2026-03-01T08-44-54Z",
+            &run_dir,
+            &criterion,
+        )
+        .expect("write bundle");
+        let manifest = verify_bundle(&bundle).expect("verify bundle");
+        assert_eq!(manifest.schema_version, SCHEMA_VERSION);
+        assert!(manifest.raw_samples);
+        assert_eq!(manifest.estimates.len(), 2);
+        assert_eq!(manifest.settings[0].name, "build");
+        assert_eq!(manifest.environment[0].name, "environment");
+        assert!(manifest.files.iter().any(|file| {
+            file.path == "criterion/non_cryptographic_hash/XXH3-64/1024/new/sample.json"
+        }));
+
+        // Newer samples than the run: bundled without them.
+        write_case(
+            &criterion,
+            "cryptographic_hash",
+            "BLAKE3-256",
+            "1024",
+            1024,
+            300.0,
+        );
+        let stale = root.join("stale");
+        write_bundle_dir(
+            &stale,
+            "apple-m4-macos",
+            "2026-03-01T08-44-54Z",
+            &run_dir,
+            &criterion,
+        )
+        .expect("write stale bundle");
+        assert!(!verify_bundle(&stale).expect("verify stale").raw_samples);
+        assert!(!stale.join("criterion").exists());
+
+        fs::write(bundle.join("run/charts/a.svg"), "<svg></svg>").expect("tamper chart");
+        let error = verify_bundle(&bundle).expect_err("tampered chart");
+        assert!(
+            error
+                .to_string()
+                .contains("checksum mismatch for run/charts/a.svg")
+        );
+        fs::write(bundle.join("run/charts/a.svg"), "<svg/>").expect("restore chart");
+
+        fs::write(bundle.join("extra.txt"), "").expect("write extra");
+        let error = verify_bundle(&bundle).expect_err("extra file");
+        assert!(error.to_string().contains("extra.txt"));
+        fs::remove_file(bundle.join("extra.txt")).expect("remove extra");
+
+        let manifest = fs::read_to_string(bundle.join(MANIFEST_FILE)).expect("read manifest");
+        fs::write(
+            bundle.join(MANIFEST_FILE),
+            manifest.replace("\"schema_version\": 1", "\"schema_version\": 99"),
+        )
+        .expect("write manifest");
+        let error = verify_bundle(&bundle).expect_err("future schema");
+        assert!(error.to_string().contains("newer than the supported"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
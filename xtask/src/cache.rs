@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use crate::{
+    criterion::{BenchmarkRecord, read_group},
+    util::{Result, format_bytes, format_bytes_rate},
+};
+
+/// Criterion group of the hash benchmarks reading from a pool larger than the LLC.
+pub const COLD_CACHE_GROUP: &str = "cold_cache_hash";
+
+/// Criterion groups holding the hot-cache counterparts of [`COLD_CACHE_GROUP`].
+const HOT_CACHE_GROUPS: &[&str] = &["non_cryptographic_hash", "cryptographic_hash"];
+
+/// Hot and cold throughput of one hasher at one input size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HotColdRate {
+    pub hot: f64,
+    pub cold: f64,
+}
+
+/// Hot vs cold throughput keyed by hasher, then input size in bytes.
+pub type HotColdTable = BTreeMap<String, BTreeMap<u64, HotColdRate>>;
+
+pub fn hot_cold_table(hot: &[BenchmarkRecord], cold: &[BenchmarkRecord]) -> HotColdTable {
+    let mut table = HotColdTable::new();
+    for cold in cold {
+        let Some(size) = record_size(cold) else {
+            continue;
+        };
+        let Some(hot) = hot
+            .iter()
+            .find(|hot| hot.function == cold.function && record_size(hot) == Some(size))
+        else {
+            continue;
+        };
+        let (Some(hot), Some(cold_rate)) = (hot.throughput_per_sec(), cold.throughput_per_sec())
+        else {
+            continue;
+        };
+        table
+            .entry(cold.function.clone())
+            .or_default()
+            .insert(size, HotColdRate {
+                hot,
+                cold: cold_rate,
+            });
+    }
+    table
+}
+
+/// Write the hot vs cold table when cold-cache results exist in `criterion_dir`.
+pub fn write_hot_cold_markdown(writer: &mut impl Write, criterion_dir: &Path) -> Result<()> {
+    let cold = read_group(criterion_dir, COLD_CACHE_GROUP)?;
+    if cold.is_empty() {
+        return Ok(());
+    }
+    let mut hot = Vec::new();
+    for group in HOT_CACHE_GROUPS {
+        hot.extend(read_group(criterion_dir, group)?);
+    }
+
+    let table = hot_cold_table(&hot, &cold);
+    let mut sizes: Vec<u64> = table.values().flat_map(|row| row.keys().copied()).collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    if sizes.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "#### Hot vs Cold Cache Throughput")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Each cell is `hot / cold`: hot re-hashes one buffer, cold reads a different slot of a \
+         pool larger than the last-level cache on every iteration."
+    )?;
+    writeln!(writer)?;
+    write!(writer, "| Hasher |")?;
+    for size in &sizes {
+        write!(writer, " {} |", format_bytes(*size))?;
+    }
+    writeln!(writer)?;
+    write!(writer, "| --- |")?;
+    for _ in &sizes {
+        write!(writer, " --- |")?;
+    }
+    writeln!(writer)?;
+    for (function, row) in &table {
+        write!(writer, "| `{function}` |")?;
+        for size in &sizes {
+            match row.get(size) {
+                Some(rate) => write!(
+                    writer,
+                    " {} / {} |",
+                    format_bytes_rate(rate.hot),
+                    format_bytes_rate(rate.cold)
+                )?,
+                None => write!(writer, " - |")?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn record_size(record: &BenchmarkRecord) -> Option<u64> {
+    record.parameter.as_deref()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hot_cold_table;
+    use crate::criterion::{Throughput, tests::record};
+
+    #[test]
+    fn hot_cold_table_pairs_function_and_size() {
+        let hot = vec![
+            record("XXH3-64", Throughput::Bytes(16), 1.0),
+            record("XXH3-64", Throughput::Bytes(256), 10.0),
+        ];
+        let cold = vec![
+            record("XXH3-64", Throughput::Bytes(16), 4.0),
+            record("SHA2-256", Throughput::Bytes(16), 100.0),
+        ];
+
+        let table = hot_cold_table(&hot, &cold);
+        assert_eq!(table.len(), 1);
+        let rate = table["XXH3-64"][&16];
+        assert_eq!(rate.hot, 16e9);
+        assert_eq!(rate.cold, 4e9);
+    }
+}
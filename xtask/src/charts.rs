@@ -0,0 +1,310 @@
+use std::{fs, path::Path};
+
+use plotters::prelude::*;
+
+use crate::{
+    criterion::{BenchmarkRecord, Throughput},
+    util::{Result, format_bytes, slugify},
+};
+
+const LINE_CHART_SIZE: (u32, u32) = (900, 520);
+const BAR_CHART_WIDTH: u32 = 900;
+const FONT: &str = "sans-serif";
+
+/// Summary records of one criterion group on one host.
+pub struct HostSeries<'a> {
+    pub title: &'a str,
+    pub records: Vec<BenchmarkRecord>,
+}
+
+/// A generated chart: alt text and file name inside the chart directory.
+pub struct Chart {
+    pub title: String,
+    pub file: String,
+}
+
+/// Charts comparing hosts for one criterion group.
+pub struct GroupCharts {
+    /// One bar chart per input size, algorithms on the y axis and a bar per host.
+    pub by_size: Vec<Chart>,
+    /// One line chart per algorithm, throughput over log2 input size with a line per host.
+    pub by_algorithm: Vec<Chart>,
+}
+
+/// Draw the comparison charts of `group` into `out_dir`.
+///
+/// Throughput is converted to one unit per group so every chart shares an axis scale:
+/// GiB/s for byte throughput, Melem/s for element throughput.
+pub fn write_group_charts(
+    out_dir: &Path,
+    group: &str,
+    group_title: &str,
+    hosts: &[HostSeries<'_>],
+) -> Result<GroupCharts> {
+    fs::create_dir_all(out_dir)?;
+    let unit = hosts
+        .iter()
+        .flat_map(|host| &host.records)
+        .find_map(|record| record.throughput)
+        .map_or(Unit::Elements, Unit::of);
+
+    let mut parameters: Vec<(u64, String)> = Vec::new();
+    let mut algorithms: Vec<&str> = Vec::new();
+    for record in hosts.iter().flat_map(|host| &host.records) {
+        if let Some(throughput) = record.throughput
+            && !parameters
+                .iter()
+                .any(|(amount, _)| *amount == throughput.amount())
+        {
+            parameters.push((throughput.amount(), record.display_parameter()));
+        }
+        if !algorithms.contains(&record.function.as_str()) {
+            algorithms.push(&record.function);
+        }
+    }
+    parameters.sort_unstable();
+
+    let mut charts = GroupCharts {
+        by_size: Vec::new(),
+        by_algorithm: Vec::new(),
+    };
+    for (amount, label) in &parameters {
+        let bars: Vec<(&str, Vec<Option<f64>>)> = algorithms
+            .iter()
+            .map(|&algorithm| {
+                let values = hosts
+                    .iter()
+                    .map(|host| {
+                        host.records
+                            .iter()
+                            .find(|record| {
+                                record.function == algorithm
+                                    && record.throughput.map(Throughput::amount) == Some(*amount)
+                            })
+                            .and_then(|record| unit.rate(record))
+                    })
+                    .collect();
+                (algorithm, values)
+            })
+            .filter(|(_, values): &(&str, Vec<Option<f64>>)| values.iter().any(Option::is_some))
+            .collect();
+        if bars.is_empty() {
+            continue;
+        }
+        let title = format!("{group_title}, {label}");
+        let file = format!("{group}-{amount}.svg");
+        draw_bar_chart(&out_dir.join(&file), &title, unit, hosts, &bars)?;
+        charts.by_size.push(Chart { title, file });
+    }
+
+    for &algorithm in &algorithms {
+        let lines: Vec<Vec<(f64, f64)>> = hosts
+            .iter()
+            .map(|host| {
+                host.records
+                    .iter()
+                    .filter(|record| record.function == algorithm)
+                    .filter_map(|record| {
+                        let amount = record.throughput?.amount() as f64;
+                        Some((amount.log2(), unit.rate(record)?))
+                    })
+                    .collect()
+            })
+            .collect();
+        let title = format!("{group_title}, {algorithm}");
+        let file = format!("{group}-{}.svg", slugify(algorithm));
+        draw_line_chart(&out_dir.join(&file), &title, unit, hosts, &lines)?;
+        charts.by_algorithm.push(Chart { title, file });
+    }
+    Ok(charts)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Unit {
+    GibPerSec,
+    Elements,
+}
+
+impl Unit {
+    fn of(throughput: Throughput) -> Self {
+        match throughput {
+            Throughput::Bytes(_) | Throughput::BytesDecimal(_) => Unit::GibPerSec,
+            Throughput::Elements(_) | Throughput::Bits(_) => Unit::Elements,
+        }
+    }
+
+    fn rate(self, record: &BenchmarkRecord) -> Option<f64> {
+        let rate = record.throughput_per_sec()?;
+        Some(match self {
+            Unit::GibPerSec => rate / (1u64 << 30) as f64,
+            Unit::Elements => rate / 1e6,
+        })
+    }
+
+    fn axis_label(self) -> &'static str {
+        match self {
+            Unit::GibPerSec => "Throughput (GiB/s)",
+            Unit::Elements => "Throughput (Melem/s)",
+        }
+    }
+
+    fn format_amount(self, amount: f64) -> String {
+        match self {
+            Unit::GibPerSec => format_bytes(amount.round() as u64),
+            Unit::Elements => format!("{}", amount.round() as u64),
+        }
+    }
+}
+
+fn draw_line_chart(
+    path: &Path,
+    title: &str,
+    unit: Unit,
+    hosts: &[HostSeries<'_>],
+    lines: &[Vec<(f64, f64)>],
+) -> Result<()> {
+    let points = || lines.iter().flatten();
+    let min_x = points().map(|&(x, _)| x).fold(f64::INFINITY, f64::min);
+    let max_x = points().map(|&(x, _)| x).fold(0.0, f64::max);
+    let max_y = points().map(|&(_, y)| y).fold(0.0, f64::max);
+    if !min_x.is_finite() {
+        return Err(format!("no data for chart `{title}`").into());
+    }
+    // A single input size still needs a non-empty range.
+    let (min_x, max_x) = if min_x < max_x {
+        (min_x, max_x)
+    } else {
+        (min_x - 1.0, max_x + 1.0)
+    };
+
+    let root = SVGBackend::new(path, LINE_CHART_SIZE).into_drawing_area();
+    root.fill(&WHITE).map_err(chart_error)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, (FONT, 22))
+        .margin(16)
+        .x_label_area_size(48)
+        .y_label_area_size(64)
+        .build_cartesian_2d(min_x..max_x, 0.0..max_y * 1.1)
+        .map_err(chart_error)?;
+    chart
+        .configure_mesh()
+        .x_desc("Input size")
+        .y_desc(unit.axis_label())
+        .x_labels((max_x - min_x).ceil() as usize + 1)
+        .x_label_formatter(&|&x| {
+            let exponent = x.round();
+            if (x - exponent).abs() > 1e-6 {
+                return String::new();
+            }
+            unit.format_amount(exponent.exp2())
+        })
+        .draw()
+        .map_err(chart_error)?;
+
+    for (index, (host, line)) in hosts.iter().zip(lines).enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let color = Palette99::pick(index).to_rgba();
+        chart
+            .draw_series(LineSeries::new(line.iter().copied(), color.stroke_width(2)))
+            .map_err(chart_error)?
+            .label(host.title)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color.stroke_width(2)));
+        chart
+            .draw_series(
+                line.iter()
+                    .map(|&point| Circle::new(point, 3, color.filled())),
+            )
+            .map_err(chart_error)?;
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.9))
+        .border_style(BLACK)
+        .draw()
+        .map_err(chart_error)?;
+    root.present().map_err(chart_error)?;
+    Ok(())
+}
+
+fn draw_bar_chart(
+    path: &Path,
+    title: &str,
+    unit: Unit,
+    hosts: &[HostSeries<'_>],
+    bars: &[(&str, Vec<Option<f64>>)],
+) -> Result<()> {
+    let max_x = bars
+        .iter()
+        .flat_map(|(_, values)| values.iter().flatten())
+        .fold(0.0, |max: f64, &value| max.max(value));
+    let row_height = 8 + 14 * hosts.len() as u32;
+    let height = 120 + row_height * bars.len() as u32;
+    let label_width = bars
+        .iter()
+        .map(|(algorithm, _)| algorithm.len() as u32)
+        .max()
+        .unwrap_or(0)
+        * 8
+        + 16;
+
+    let root = SVGBackend::new(path, (BAR_CHART_WIDTH, height)).into_drawing_area();
+    root.fill(&WHITE).map_err(chart_error)?;
+    let rows = bars.len() as f64;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, (FONT, 22))
+        .margin(16)
+        .x_label_area_size(48)
+        .y_label_area_size(label_width)
+        .build_cartesian_2d(0.0..max_x * 1.1, -0.5..rows - 0.5)
+        .map_err(chart_error)?;
+    chart
+        .configure_mesh()
+        .disable_y_mesh()
+        .y_labels(bars.len())
+        .y_label_formatter(&|&y| {
+            let row = y.round();
+            if (y - row).abs() > 1e-6 || row < 0.0 || row as usize >= bars.len() {
+                return String::new();
+            }
+            // Row 0 is at the bottom; list algorithms top to bottom in input order.
+            bars[bars.len() - 1 - row as usize].0.to_owned()
+        })
+        .x_desc(unit.axis_label())
+        .draw()
+        .map_err(chart_error)?;
+
+    let bar_height = 0.8 / hosts.len() as f64;
+    for (index, host) in hosts.iter().enumerate() {
+        let color = Palette99::pick(index).to_rgba();
+        let rects = bars.iter().enumerate().filter_map(|(row, (_, values))| {
+            let value = values[index]?;
+            let center = (bars.len() - 1 - row) as f64;
+            let top = center + 0.4 - bar_height * index as f64;
+            Some(Rectangle::new(
+                [(0.0, top), (value, top - bar_height)],
+                color.filled(),
+            ))
+        });
+        chart
+            .draw_series(rects)
+            .map_err(chart_error)?
+            .label(host.title)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 14, y + 5)], color.filled()));
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .background_style(WHITE.mix(0.9))
+        .border_style(BLACK)
+        .draw()
+        .map_err(chart_error)?;
+    root.present().map_err(chart_error)?;
+    Ok(())
+}
+
+fn chart_error(error: impl std::fmt::Display) -> Box<dyn std::error::Error> {
+    format!("failed to draw chart: {error}").into()
+}
@@ -1,4 +1,6 @@
-use clap::{Args, Parser, ValueEnum};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 use crate::scope::Scope;
 
@@ -12,7 +14,28 @@ pub enum Command {
     Collect(CollectOpts),
 
     /// Aggregate results from all hosts
-    Aggregate,
+    Aggregate(AggregateOpts),
+
+    /// Print the throughput of one benchmark across the collected runs of a host
+    History(HistoryOpts),
+
+    /// Build a static HTML report site from `results/`
+    Report(ReportOpts),
+
+    /// Export the collected results of all hosts as one tidy dataset
+    Export(ExportOpts),
+
+    /// Add criterion results, a collected run or a bundle from another machine to `results/`
+    Import(ImportOpts),
+
+    /// Create or verify a portable bundle of one collected run
+    Bundle(BundleOpts),
+
+    /// Upgrade the `environment.ini` of every collected run to the current schema
+    MigrateResults(MigrateResultsOpts),
+
+    /// Recompute the estimates of collected runs from their stored raw samples
+    Reanalyze(ReanalyzeOpts),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -21,6 +44,26 @@ pub struct BenchOpts {
     #[arg(long)]
     pub quick: bool,
 
+    /// What each benchmark iteration is measured in
+    #[arg(long, value_enum, default_value_t = MeasurementValue::Wall)]
+    pub measurement: MeasurementValue,
+
+    /// Refuse to run when pre-flight checks find a noisy environment
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Pin the benchmark binaries to these CPUs, e.g. `2,3` or `4-7` (Linux only)
+    #[arg(long, value_name = "LIST", conflicts_with = "isolate")]
+    pub cpu: Option<String>,
+
+    /// Pin the benchmark binaries to the kernel-isolated CPUs (`isolcpus=`)
+    #[arg(long)]
+    pub isolate: bool,
+
+    /// Niceness of the benchmark binaries; negative values need root (Linux only)
+    #[arg(long, value_name = "N", allow_negative_numbers = true)]
+    pub nice: Option<i32>,
+
     /// Extra arguments to pass to `cargo bench`
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     pub bench_extra_args: Vec<String>,
@@ -32,6 +75,11 @@ pub struct RunOpts {
     #[arg(long, value_enum, default_value_t = ScopeValue::All)]
     pub scope: ScopeValue,
 
+    /// Run every scope once per rustup toolchain, e.g. `stable,beta,nightly,1.89`, into
+    /// `target/toolchains/{toolchain}` and compare their throughput
+    #[arg(long, value_delimiter = ',', value_name = "TOOLCHAINS")]
+    pub toolchain: Vec<String>,
+
     #[command(flatten)]
     pub bench_args: BenchOpts,
 }
@@ -42,10 +90,254 @@ pub struct CollectOpts {
     #[arg(short, long)]
     pub run_bench: bool,
 
+    /// How the `results/` directory of this host is named
+    #[arg(long, value_enum, default_value_t = HostIdValue::Cpu)]
+    pub host_id: HostIdValue,
+
+    /// Appended to the host id to tell apart machines with the same CPU, e.g. `desktop`
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Add results to a host directory recorded by a different machine
+    #[arg(long)]
+    pub force: bool,
+
+    /// Algorithm relative-throughput tables are normalized to, e.g. `XXH3-64` or `XOR-128-*`;
+    /// repeatable, the first one present in a group is used
+    #[arg(long = "reference", value_name = "ALGORITHM")]
+    pub references: Vec<String>,
+
     #[command(flatten)]
     pub bench_args: BenchOpts,
 }
 
+#[derive(Debug, Clone, Args)]
+pub struct AggregateOpts {
+    /// Algorithm relative-throughput tables are normalized to, e.g. `XXH3-64` or `XOR-128-*`;
+    /// repeatable, the first one present in a group is used
+    #[arg(long = "reference", value_name = "ALGORITHM")]
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HostIdValue {
+    /// CPU model only, e.g. `apple-m4`
+    Cpu,
+    /// CPU model and OS, e.g. `apple-m4-macos`
+    CpuOs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct HistoryOpts {
+    /// Benchmark scope to show
+    #[arg(long, value_enum, default_value_t = ScopeValue::All)]
+    pub scope: ScopeValue,
+
+    /// Benchmark function name, e.g. `XXH3-64`
+    #[arg(long)]
+    pub bench: String,
+
+    /// Host directory under `results/`; defaults to the current host
+    #[arg(long)]
+    pub host: Option<String>,
+
+    #[command(flatten)]
+    pub current_host: CurrentHostOpts,
+}
+
+/// How the current host's directory is found when `--host` is not given, as `collect`
+/// names it.
+#[derive(Debug, Clone, Args)]
+pub struct CurrentHostOpts {
+    /// How the `results/` directory of this host is named
+    #[arg(long, value_enum, default_value_t = HostIdValue::Cpu, conflicts_with = "host")]
+    pub host_id: HostIdValue,
+
+    /// Label this host was collected with, e.g. `desktop`
+    #[arg(long, conflicts_with = "host")]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ReportOpts {
+    /// Directory the site is written to, relative to the workspace root
+    #[arg(long, default_value = "target/report")]
+    pub out: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ImportOpts {
+    /// A `target/criterion` directory, a run directory written by `collect`, a bundle, or
+    /// a tar archive of any of them
+    pub path: PathBuf,
+
+    /// `environment.ini` of the machine that ran the benchmarks, if the criterion directory at
+    /// `path` has none
+    #[arg(long, value_name = "FILE")]
+    pub environment: Option<PathBuf>,
+
+    /// How the `results/` directory of the imported host is named
+    #[arg(long, value_enum, default_value_t = HostIdValue::Cpu)]
+    pub host_id: HostIdValue,
+
+    /// Appended to the host id, replacing the label recorded in the environment file
+    #[arg(long)]
+    pub label: Option<String>,
+
+    /// Add results to a host directory recorded by a different machine, or replace a run
+    /// imported before
+    #[arg(long)]
+    pub force: bool,
+
+    /// Algorithm relative-throughput tables are normalized to, e.g. `XXH3-64` or `XOR-128-*`;
+    /// repeatable, the first one present in a group is used
+    #[arg(long = "reference", value_name = "ALGORITHM")]
+    pub references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BundleOpts {
+    #[command(subcommand)]
+    pub command: BundleCommand,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum BundleCommand {
+    /// Bundle a run with its manifest, checksums and, when still available, raw samples
+    Create(BundleCreateOpts),
+
+    /// Check a bundle's manifest, checksums and estimates
+    Verify(BundleVerifyOpts),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BundleCreateOpts {
+    /// Host directory under `results/`; defaults to the current host
+    #[arg(long)]
+    pub host: Option<String>,
+
+    #[command(flatten)]
+    pub current_host: CurrentHostOpts,
+
+    /// Run id, e.g. `2026-03-01T08-44-54Z`; defaults to the latest run
+    #[arg(long)]
+    pub run: Option<String>,
+
+    /// Bundle to write: a `.tar.zst` archive or a directory; defaults to
+    /// `target/bundles/{host}-{run}.tar.zst`
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BundleVerifyOpts {
+    /// Bundle directory or `.tar.zst` archive
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ExportOpts {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+    pub format: ExportFormat,
+
+    /// File to write instead of stdout
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+
+    /// Only export the run each host publishes, instead of all its runs
+    #[arg(long)]
+    pub latest: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON array of row objects
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct MigrateResultsOpts {
+    /// Only report files that need migrating, and fail if there are any
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ReanalyzeOpts {
+    /// Host directory under `results/`; defaults to every host
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Run id, e.g. `2026-03-01T08-44-54Z`; defaults to every run with raw samples
+    #[arg(long)]
+    pub run: Option<String>,
+
+    /// Confidence level of the bootstrap intervals
+    #[arg(long, default_value_t = 0.95)]
+    pub confidence: f64,
+
+    /// Bootstrap resamples per estimate
+    #[arg(long, default_value_t = 100_000)]
+    pub resamples: usize,
+
+    /// Seed of the bootstrap resampling
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// What to do with measurements outside Tukey's fences
+    #[arg(long, value_enum, default_value_t = OutlierPolicy::Keep)]
+    pub outliers: OutlierPolicy,
+
+    /// Save the recomputed estimates to `reanalysis.json`, next to `summary.json`
+    #[arg(long)]
+    pub write: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutlierPolicy {
+    /// Count outliers but estimate from every measurement, like criterion
+    Keep,
+    /// Drop measurements beyond the outer fences
+    DropSevere,
+    /// Drop measurements beyond the inner fences
+    DropAll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MeasurementValue {
+    /// Wall-clock time (criterion default)
+    Wall,
+    /// Package energy from Linux RAPL counters; skipped when unavailable
+    Energy,
+    /// CPU cycles from `perf_event_open`; skipped when unavailable
+    Cycles,
+    /// Retired instructions from `perf_event_open`; skipped when unavailable
+    Instructions,
+    /// Cache misses from `perf_event_open`; skipped when unavailable
+    CacheMisses,
+    /// Cycles, instructions and cache misses, one benchmark run each
+    Perf,
+}
+
+impl MeasurementValue {
+    /// Values of `BENCH_MEASUREMENT` understood by `bench_support::bench_main`, one per run.
+    pub fn env_values(self) -> &'static [&'static str] {
+        match self {
+            MeasurementValue::Wall => &["wall"],
+            MeasurementValue::Energy => &["energy"],
+            MeasurementValue::Cycles => &["cycles"],
+            MeasurementValue::Instructions => &["instructions"],
+            MeasurementValue::CacheMisses => &["cache-misses"],
+            MeasurementValue::Perf => &["cycles", "instructions", "cache-misses"],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum ScopeValue {
     Hash,
@@ -5,11 +5,22 @@ use std::{
 };
 
 use crate::{
-    bench,
+    alignment, bench,
+    build_config::BuildConfig,
+    cache,
     cli::{CollectOpts, RunOpts, ScopeValue},
-    environment::BenchmarkEnvironment,
+    criterion::{criterion_dir, output_stamp},
+    energy,
+    environment::{BenchmarkEnvironment, ENV_METADATA_FILE, try_read_metadata},
+    normalize, perf,
+    placement::Placement,
+    preflight::PreflightReport,
+    runs::{self, RUNS_DIR},
+    samples,
     scope::Scope,
-    util::{Result, run_capture, workspace_root},
+    toolchains,
+    util::{Result, run_capture, slugify, workspace_root},
+    versions::LibraryVersions,
 };
 
 pub fn collect_results(args: CollectOpts) -> Result<()> {
@@ -18,12 +29,44 @@ pub fn collect_results(args: CollectOpts) -> Result<()> {
     if args.run_bench {
         let run_args = RunOpts {
             scope: ScopeValue::All,
+            toolchain: Vec::new(),
             bench_args: args.bench_args.clone(),
         };
         bench::run_benchmarks(&run_args)?;
     }
 
-    let environment = BenchmarkEnvironment::detect();
+    if let Some(label) = &args.label
+        && slugify(label).is_empty()
+    {
+        return Err(
+            format!("invalid --label `{label}`: needs at least one letter or digit").into(),
+        );
+    }
+
+    let stamp = output_stamp(&criterion_dir(workspace_root));
+    let build = BuildConfig::load_last(workspace_root, stamp.as_deref());
+    if build.is_none() {
+        eprintln!(
+            "note: target/criterion was not written by `xtask run`; the build configuration is \
+             not recorded"
+        );
+    }
+    // Versions of the build that wrote the results; `Cargo.lock` may have changed since.
+    let versions = LibraryVersions::load_last(workspace_root, stamp.as_deref()).or_else(|| {
+        match LibraryVersions::resolve(workspace_root) {
+            Ok(versions) => Some(versions),
+            Err(error) => {
+                eprintln!("warning: cannot resolve library versions: {error}");
+                None
+            }
+        }
+    });
+    let environment = BenchmarkEnvironment::detect()
+        .with_label(args.label.clone())
+        .with_build(build)
+        .with_versions(versions)
+        .with_placement(Placement::load_last(workspace_root, stamp.as_deref()))
+        .with_preflight(PreflightReport::load_last(workspace_root, stamp.as_deref()));
     let result_dir = collect_host_results(&args, workspace_root, &environment)?;
     println!("{}", result_dir.display());
 
@@ -35,32 +78,97 @@ fn collect_host_results(
     workspace_root: &Path,
     environment: &BenchmarkEnvironment,
 ) -> Result<PathBuf> {
-    let results_root = workspace_root.join("results");
-    fs::create_dir_all(&results_root)?;
+    let host_dir = workspace_root
+        .join("results")
+        .join(environment.result_name(args.host_id));
+    check_host_identity(&host_dir, environment, args.force)?;
+    for &scope in Scope::all() {
+        bench::ensure_chart_artifacts(scope, workspace_root)?;
+    }
+
+    let criterion_dir = criterion_dir(workspace_root);
+    add_run(&host_dir, &runs::new_run_id(), |run_dir| {
+        copy_charts(&criterion_dir, run_dir)?;
+        write_host_readme(run_dir, &criterion_dir, args, environment)?;
+        environment.write_metadata_file(run_dir)?;
+        samples::keep_samples(&criterion_dir, run_dir);
+        toolchains::collect_toolchains(workspace_root, run_dir)?;
+        runs::write_summary(run_dir, &criterion_dir)
+    })
+}
+
+/// Add the run `run_id` to `host_dir` and publish it as the latest one.
+///
+/// `fill` writes the run into a staging directory, so an interrupted run never replaces
+/// a complete one.
+pub fn add_run(
+    host_dir: &Path,
+    run_id: &str,
+    fill: impl FnOnce(&Path) -> Result<()>,
+) -> Result<PathBuf> {
+    if let Some(results_root) = host_dir.parent() {
+        fs::create_dir_all(results_root)?;
+    }
+    runs::migrate_legacy_layout(host_dir)?;
 
-    let host_id = environment.result_name();
-    let result_dir = results_root.join(&host_id);
-    let staging_dir = results_root.join(format!(".{host_id}.tmp"));
+    let runs_dir = host_dir.join(RUNS_DIR);
+    let result_dir = runs_dir.join(run_id);
+    let staging_dir = runs_dir.join(format!(".{run_id}.tmp"));
     if staging_dir.is_dir() {
         fs::remove_dir_all(&staging_dir)?;
     }
+    fs::create_dir_all(&staging_dir)?;
 
-    let charts_dir = staging_dir.join("charts");
-    fs::create_dir_all(&charts_dir)?;
+    fill(&staging_dir)?;
+    replace_result_dir(&staging_dir, &result_dir)?;
+    runs::set_latest(host_dir, run_id)?;
 
+    Ok(result_dir)
+}
+
+/// Copy the criterion charts every scope publishes into `run_dir/charts`.
+pub fn copy_charts(criterion_dir: &Path, run_dir: &Path) -> Result<()> {
+    let charts_dir = run_dir.join("charts");
+    fs::create_dir_all(&charts_dir)?;
     for &scope in Scope::all() {
-        bench::ensure_chart_artifacts(scope, workspace_root)?;
         for chart in scope.charts() {
-            let src = workspace_root.join(chart.src_path);
-            fs::copy(src, charts_dir.join(chart.dest_path))?;
+            fs::copy(
+                chart.source(criterion_dir),
+                charts_dir.join(chart.dest_path),
+            )?;
         }
     }
+    Ok(())
+}
 
-    write_host_readme(&staging_dir, args, environment)?;
-    environment.write_metadata_file(&staging_dir)?;
-    replace_result_dir(&staging_dir, &result_dir)?;
-
-    Ok(result_dir)
+/// Refuse to add results to a host directory recorded by another machine, unless forced.
+pub fn check_host_identity(
+    host_dir: &Path,
+    environment: &BenchmarkEnvironment,
+    force: bool,
+) -> Result<()> {
+    // A file this xtask cannot read may well be another machine's, so it counts as one.
+    let mismatch = match try_read_metadata(&runs::latest_run_dir(host_dir)) {
+        Ok(None) => return Ok(()),
+        Ok(Some(recorded)) => match environment.machine_mismatch(&recorded) {
+            Some(mismatch) => mismatch,
+            None => return Ok(()),
+        },
+        Err(error) => format!("an unreadable {ENV_METADATA_FILE} ({error})"),
+    };
+    if force {
+        eprintln!(
+            "warning: {} was recorded with {mismatch}; adding results anyway (--force)",
+            host_dir.display()
+        );
+        return Ok(());
+    }
+    Err(format!(
+        "{} was recorded with {mismatch}; pass --label to give this machine its own \
+         directory, or --force to add results to it anyway",
+        host_dir.display()
+    )
+    .into())
 }
 
 fn replace_result_dir(staging_dir: &Path, result_dir: &Path) -> Result<()> {
@@ -73,6 +181,7 @@ fn replace_result_dir(staging_dir: &Path, result_dir: &Path) -> Result<()> {
 
 fn write_host_readme(
     result_dir: &Path,
+    criterion_dir: &Path,
     args: &CollectOpts,
     environment: &BenchmarkEnvironment,
 ) -> Result<()> {
@@ -110,6 +219,16 @@ fn write_host_readme(
         )?;
     }
 
+    write_results_markdown(&mut file, criterion_dir, &args.references, environment)
+}
+
+/// `## Results` section of a host README: charts and tables of every scope.
+pub fn write_results_markdown(
+    file: &mut impl Write,
+    criterion_dir: &Path,
+    references: &[String],
+    environment: &BenchmarkEnvironment,
+) -> Result<()> {
     writeln!(file)?;
     writeln!(file, "## Results")?;
 
@@ -122,6 +241,17 @@ fn write_host_readme(
             writeln!(file)?;
             writeln!(file, "![{}](charts/{})", chart.title, chart.dest_path)?;
         }
+        let references = normalize::references(scope, references);
+        normalize::write_relative_markdown(file, criterion_dir, scope, &references)?;
+        if scope == Scope::Hash {
+            alignment::write_penalty_markdown(file, criterion_dir)?;
+            cache::write_hot_cold_markdown(file, criterion_dir)?;
+        }
+        perf::write_counter_markdown(file, criterion_dir, scope)?;
+        energy::write_energy_markdown(file, criterion_dir, scope)?;
+        if let Some(versions) = environment.versions() {
+            versions.write_markdown(file, scope)?;
+        }
     }
 
     Ok(())
@@ -142,9 +272,11 @@ mod tests {
         time::{SystemTime, UNIX_EPOCH},
     };
 
-    use super::{replace_result_dir, write_host_readme};
+    use super::{check_host_identity, replace_result_dir, write_host_readme};
     use crate::{
-        cli::{BenchOpts, CollectOpts},
+        alignment::UNALIGNED_GROUP,
+        cli::{BenchOpts, CollectOpts, HostIdValue, MeasurementValue},
+        criterion::tests::write_case,
         environment::BenchmarkEnvironment,
     };
 
@@ -155,13 +287,22 @@ mod tests {
 
         let args = CollectOpts {
             run_bench: false,
+            host_id: HostIdValue::CpuOs,
+            label: None,
+            force: false,
+            references: vec![],
             bench_args: BenchOpts {
                 quick: true,
+                measurement: MeasurementValue::Wall,
+                strict: false,
+                cpu: None,
+                isolate: false,
+                nice: None,
                 bench_extra_args: vec![],
             },
         };
         let env = BenchmarkEnvironment::detect();
-        write_host_readme(&temp, &args, &env).expect("write README");
+        write_host_readme(&temp, &temp.join("criterion"), &args, &env).expect("write README");
 
         let readme = fs::read_to_string(temp.join("README.md")).expect("read README");
         let env_idx = readme.find("## Environment").expect("missing Environment");
@@ -178,6 +319,75 @@ mod tests {
         fs::remove_dir_all(temp).expect("cleanup");
     }
 
+    #[test]
+    fn readme_includes_unaligned_penalty_table() {
+        let temp = temp_dir("collect-unaligned");
+        let criterion = temp.join("criterion");
+        write_case(&criterion, UNALIGNED_GROUP, "XXH3-64", "0", 4096, 100.0);
+        write_case(&criterion, UNALIGNED_GROUP, "XXH3-64", "1", 4096, 125.0);
+
+        let args = CollectOpts {
+            run_bench: false,
+            host_id: HostIdValue::CpuOs,
+            label: None,
+            force: false,
+            references: vec![],
+            bench_args: BenchOpts {
+                quick: false,
+                measurement: MeasurementValue::Wall,
+                strict: false,
+                cpu: None,
+                isolate: false,
+                nice: None,
+                bench_extra_args: vec![],
+            },
+        };
+        let env = BenchmarkEnvironment::detect();
+        write_host_readme(&temp, &criterion, &args, &env).expect("write README");
+
+        let readme = fs::read_to_string(temp.join("README.md")).expect("read README");
+        let table_idx = readme
+            .find("#### Unaligned Input Penalty")
+            .expect("missing penalty table");
+        assert!(table_idx < readme.find("### PRNG").expect("missing PRNG"));
+        assert!(readme.contains("| `XXH3-64` | 38.15 GiB/s | +25.0% | +25.0% | 1 |"));
+
+        fs::remove_dir_all(temp).expect("cleanup");
+    }
+
+    #[test]
+    fn host_identity_refuses_other_machine_unless_forced() {
+        let host = temp_dir("collect-identity");
+        let run = host.join("runs/2026-03-01T00-00-00Z");
+        fs::create_dir_all(&run).expect("create run");
+        let env = BenchmarkEnvironment::detect();
+        assert!(check_host_identity(&host, &env, false).is_ok());
+
+        fs::write(
+            run.join("environment.ini"),
+            "[environment]\ncpu = Some Other CPU\n",
+        )
+        .expect("write meta");
+        let error = check_host_identity(&host, &env, false).expect_err("different CPU");
+        assert!(error.to_string().contains("--label"));
+        assert!(check_host_identity(&host, &env, true).is_ok());
+
+        // Metadata that fails validation no longer proves it is this machine's.
+        fs::write(
+            run.join("environment.ini"),
+            format!("[environment]\ncpu = {}\nunknown_key = 1\n", env.cpu()),
+        )
+        .expect("write meta");
+        let error = check_host_identity(&host, &env, false).expect_err("invalid metadata");
+        assert!(
+            error.to_string().contains("unreadable environment.ini"),
+            "{error}"
+        );
+        assert!(check_host_identity(&host, &env, true).is_ok());
+
+        fs::remove_dir_all(host).expect("cleanup");
+    }
+
     #[test]
     fn replace_result_dir_overwrites_old_content() {
         let root = temp_dir("collect-replace");
@@ -0,0 +1,349 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::util::{Result, format_bytes, format_bytes_rate};
+
+pub const CRITERION_DIR: &str = "target/criterion";
+
+/// One benchmark case as recorded by criterion under `{group}/{function}/{value}/new`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRecord {
+    pub function: String,
+    pub parameter: Option<String>,
+    pub throughput: Option<Throughput>,
+    /// Mean per iteration: ns for wall time, see [`measurement_dir`] for the others.
+    pub mean: Estimate,
+}
+
+impl BenchmarkRecord {
+    /// Throughput of the mean estimate, in units per second.
+    pub fn throughput_per_sec(&self) -> Option<f64> {
+        let amount = self.throughput?.amount() as f64;
+        if self.mean.point <= 0.0 {
+            return None;
+        }
+        Some(amount * 1e9 / self.mean.point)
+    }
+
+    /// Mean throughput for tables, e.g. `12.34 GiB/s` or `150.00 Melem/s`.
+    pub fn display_throughput(&self) -> Option<String> {
+        let rate = self.throughput_per_sec()?;
+        Some(match self.throughput? {
+            Throughput::Bytes(_) | Throughput::BytesDecimal(_) => format_bytes_rate(rate),
+            Throughput::Elements(_) => format!("{:.2} Melem/s", rate / 1e6),
+            Throughput::Bits(_) => format!("{:.2} Gbit/s", rate / 1e9),
+        })
+    }
+
+    /// Parameter for tables: input size for byte throughput, the raw value otherwise.
+    pub fn display_parameter(&self) -> String {
+        match (self.throughput, self.parameter.as_deref()) {
+            (Some(Throughput::Bytes(bytes) | Throughput::BytesDecimal(bytes)), _) => {
+                format_bytes(bytes)
+            }
+            (_, Some(parameter)) => parameter.to_owned(),
+            (_, None) => "-".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Throughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+    Bits(u64),
+}
+
+impl Throughput {
+    pub fn amount(self) -> u64 {
+        match self {
+            Throughput::Bytes(n)
+            | Throughput::BytesDecimal(n)
+            | Throughput::Elements(n)
+            | Throughput::Bits(n) => n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub point: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Measurements behind the estimates of one case, criterion's `sample.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sample {
+    /// `Linear` when the iteration count grows across measurements, `Flat` otherwise.
+    pub sampling_mode: String,
+    /// Iterations of each measurement.
+    pub iters: Vec<f64>,
+    /// Total time of each measurement, in the unit of [`BenchmarkRecord::mean`].
+    pub times: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawBenchmark {
+    function_id: String,
+    value_str: Option<String>,
+    throughput: Option<Throughput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEstimates {
+    mean: RawEstimate,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEstimate {
+    confidence_interval: RawConfidenceInterval,
+    point_estimate: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawConfidenceInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+pub fn criterion_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(CRITERION_DIR)
+}
+
+/// Criterion output of `--measurement {measurement}` runs next to the wall-time
+/// `criterion_dir`, e.g. `criterion-energy`.
+///
+/// Means read from here are in the unit of that measurement (µJ, cycles, ...) instead of
+/// nanoseconds. Must match `bench_support::MeasurementKind::criterion_dir_name`.
+pub fn measurement_dir(criterion_dir: &Path, measurement: &str) -> PathBuf {
+    criterion_dir.with_file_name(format!("criterion-{measurement}"))
+}
+
+/// Read every benchmark of `group`, sorted by function then parameter.
+///
+/// Returns an empty list when the group has not been run.
+pub fn read_group(criterion_dir: &Path, group: &str) -> Result<Vec<BenchmarkRecord>> {
+    let mut records = Vec::new();
+    for dir in group_case_dirs(criterion_dir, group)? {
+        records.push(read_record(&dir)?);
+    }
+    records.sort_by(compare_records);
+    Ok(records)
+}
+
+/// Every benchmark of `group` with its samples, sorted like [`read_group`].
+///
+/// Cases without `sample.json` are skipped.
+pub fn read_group_samples(
+    criterion_dir: &Path,
+    group: &str,
+) -> Result<Vec<(BenchmarkRecord, Sample)>> {
+    let mut cases = Vec::new();
+    for dir in group_case_dirs(criterion_dir, group)? {
+        if dir.join("sample.json").is_file() {
+            cases.push((read_record(&dir)?, read_json(&dir.join("sample.json"))?));
+        }
+    }
+    cases.sort_by(|(a, _), (b, _)| compare_records(a, b));
+    Ok(cases)
+}
+
+fn group_case_dirs(criterion_dir: &Path, group: &str) -> Result<Vec<PathBuf>> {
+    let group_dir = criterion_dir.join(group);
+    let mut case_dirs = Vec::new();
+    if group_dir.is_dir() {
+        find_case_dirs(&group_dir, &mut case_dirs)?;
+    }
+    Ok(case_dirs)
+}
+
+/// Case directories (`.../new`) under `dir`, skipping criterion's `report` directories.
+pub fn find_case_dirs(dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    if dir.join("new").join("benchmark.json").is_file() {
+        out.push(dir.join("new"));
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() && path.file_name().is_some_and(|name| name != "report") {
+            find_case_dirs(&path, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fingerprint of the results in `criterion_dir`: the number of cases and the newest
+/// `estimates.json` among them, so any later benchmark run changes it.
+///
+/// `None` when there are no results.
+pub fn output_stamp(criterion_dir: &Path) -> Option<String> {
+    let mut case_dirs = Vec::new();
+    find_case_dirs(criterion_dir, &mut case_dirs).ok()?;
+    let newest = case_dirs
+        .iter()
+        .filter_map(|dir| {
+            fs::metadata(dir.join("estimates.json"))
+                .ok()?
+                .modified()
+                .ok()
+        })
+        .max()?;
+    let newest = newest.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    Some(format!("{} cases, newest {newest}", case_dirs.len()))
+}
+
+fn read_record(case_dir: &Path) -> Result<BenchmarkRecord> {
+    let benchmark: RawBenchmark = read_json(&case_dir.join("benchmark.json"))?;
+    let estimates: RawEstimates = read_json(&case_dir.join("estimates.json"))?;
+    Ok(BenchmarkRecord {
+        function: benchmark.function_id,
+        parameter: benchmark.value_str,
+        throughput: benchmark.throughput,
+        mean: Estimate {
+            point: estimates.mean.point_estimate,
+            lower: estimates.mean.confidence_interval.lower_bound,
+            upper: estimates.mean.confidence_interval.upper_bound,
+        },
+    })
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let raw = fs::read_to_string(path)?;
+    serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {error}", path.display()).into())
+}
+
+fn compare_records(a: &BenchmarkRecord, b: &BenchmarkRecord) -> std::cmp::Ordering {
+    a.function
+        .cmp(&b.function)
+        .then_with(|| compare_parameters(a.parameter.as_deref(), b.parameter.as_deref()))
+}
+
+/// Order numeric parameters numerically and everything else lexically.
+fn compare_parameters(a: Option<&str>, b: Option<&str>) -> std::cmp::Ordering {
+    match (
+        a.and_then(|a| a.parse::<u64>().ok()),
+        b.and_then(|b| b.parse::<u64>().ok()),
+    ) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => a.cmp(&b),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{BenchmarkRecord, Estimate, Throughput, read_group};
+
+    /// A record with an exact `mean`, its parameter being the throughput amount.
+    pub(crate) fn record(function: &str, throughput: Throughput, mean: f64) -> BenchmarkRecord {
+        BenchmarkRecord {
+            function: function.to_owned(),
+            parameter: Some(throughput.amount().to_string()),
+            throughput: Some(throughput),
+            mean: Estimate {
+                point: mean,
+                lower: mean,
+                upper: mean,
+            },
+        }
+    }
+
+    /// Write a minimal criterion case directory for tests.
+    pub fn write_case(
+        criterion_dir: &Path,
+        group: &str,
+        function: &str,
+        parameter: &str,
+        bytes: u64,
+        mean_ns: f64,
+    ) {
+        let dir = criterion_dir
+            .join(group)
+            .join(function)
+            .join(parameter)
+            .join("new");
+        fs::create_dir_all(&dir).expect("create case dir");
+        fs::write(
+            dir.join("benchmark.json"),
+            format!(
+                r#"{{"group_id":"{group}","function_id":"{function}","value_str":"{parameter}","throughput":{{"Bytes":{bytes}}}}}"#
+            ),
+        )
+        .expect("write benchmark.json");
+        fs::write(
+            dir.join("estimates.json"),
+            format!(
+                r#"{{"mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":{lo},"upper_bound":{hi}}},"point_estimate":{mean_ns},"standard_error":1.0}}}}"#,
+                lo = mean_ns * 0.9,
+                hi = mean_ns * 1.1,
+            ),
+        )
+        .expect("write estimates.json");
+    }
+
+    /// Add a `sample.json` to a case written by [`write_case`].
+    pub fn write_sample(
+        criterion_dir: &Path,
+        group: &str,
+        function: &str,
+        parameter: &str,
+        iters: &[f64],
+        times: &[f64],
+    ) {
+        let dir = criterion_dir
+            .join(group)
+            .join(function)
+            .join(parameter)
+            .join("new");
+        let sample = serde_json::json!({
+            "sampling_mode": "Linear",
+            "iters": iters,
+            "times": times,
+        });
+        fs::write(dir.join("sample.json"), sample.to_string()).expect("write sample.json");
+    }
+
+    #[test]
+    fn read_group_sorts_numeric_parameters() {
+        let root = temp_dir("criterion-read");
+        write_case(&root, "g", "XXH3-64", "256", 256, 10.0);
+        write_case(&root, "g", "XXH3-64", "16", 16, 2.0);
+        fs::create_dir_all(root.join("g/report")).expect("create report dir");
+
+        let records = read_group(&root, "g").expect("read group");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].parameter.as_deref(), Some("16"));
+        assert_eq!(records[1].throughput, Some(Throughput::Bytes(256)));
+        assert_eq!(records[1].throughput_per_sec(), Some(256.0 * 1e9 / 10.0));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn read_group_missing_is_empty() {
+        let root = temp_dir("criterion-missing");
+        let records = read_group(&root, "missing").expect("read group");
+        assert!(records.is_empty());
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
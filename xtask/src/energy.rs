@@ -0,0 +1,128 @@
+use std::{io::Write, path::Path};
+
+use crate::{
+    criterion::{BenchmarkRecord, Throughput, measurement_dir, read_group},
+    scope::Scope,
+    util::Result,
+};
+
+/// Lowest energy per unit of work one algorithm reached in one group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyEfficiency {
+    pub function: String,
+    pub group: String,
+    /// Joules per [`EnergyEfficiency::unit`].
+    pub joules: f64,
+    pub unit: &'static str,
+    /// Parameter (input size or count) at which `joules` was measured.
+    pub parameter: String,
+}
+
+/// Energy per GiB / Gelem / Gbit of one record whose mean is in microjoules.
+pub fn joules_per_unit(record: &BenchmarkRecord) -> Option<(f64, &'static str)> {
+    let (scale, unit) = match record.throughput? {
+        Throughput::Bytes(_) | Throughput::BytesDecimal(_) => ((1u64 << 30) as f64, "J/GiB"),
+        Throughput::Elements(_) => (1e9, "J/Gelem"),
+        Throughput::Bits(_) => (1e9, "J/Gbit"),
+    };
+    let amount = record.throughput?.amount() as f64;
+    if amount <= 0.0 || record.mean.point <= 0.0 {
+        return None;
+    }
+    Some((record.mean.point * 1e-6 * scale / amount, unit))
+}
+
+pub fn best_efficiency(group: &str, records: &[BenchmarkRecord]) -> Vec<EnergyEfficiency> {
+    let mut best: Vec<EnergyEfficiency> = Vec::new();
+    for record in records {
+        let Some((joules, unit)) = joules_per_unit(record) else {
+            continue;
+        };
+        let parameter = record.display_parameter();
+        match best.iter_mut().find(|e| e.function == record.function) {
+            Some(entry) if joules < entry.joules => {
+                entry.joules = joules;
+                entry.unit = unit;
+                entry.parameter = parameter;
+            }
+            Some(_) => {}
+            None => best.push(EnergyEfficiency {
+                function: record.function.clone(),
+                group: group.to_owned(),
+                joules,
+                unit,
+                parameter,
+            }),
+        }
+    }
+    best
+}
+
+/// Write the energy table when `--measurement energy` results exist next to `criterion_dir`.
+pub fn write_energy_markdown(
+    writer: &mut impl Write,
+    criterion_dir: &Path,
+    scope: Scope,
+) -> Result<()> {
+    let energy_dir = measurement_dir(criterion_dir, "energy");
+    let mut rows = Vec::new();
+    for group in scope.summary_groups() {
+        rows.extend(best_efficiency(group, &read_group(&energy_dir, group)?));
+    }
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "#### Energy Efficiency")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Lowest package energy (RAPL) per unit of work over all measured sizes; lower is better. \
+         Includes the idle power of the whole package during the run."
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "| Algorithm | Group | Energy | At |")?;
+    writeln!(writer, "| --- | --- | --- | --- |")?;
+    for row in rows {
+        writeln!(
+            writer,
+            "| `{}` | `{}` | {:.3} {} | {} |",
+            row.function, row.group, row.joules, row.unit, row.parameter
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_efficiency, joules_per_unit};
+    use crate::criterion::{Throughput, tests::record};
+
+    #[test]
+    fn joules_per_gib_and_gelem() {
+        let bytes = record("XXH3-64", Throughput::Bytes(1 << 20), 500.0);
+        let (joules, unit) = joules_per_unit(&bytes).expect("bytes throughput");
+        assert!((joules - 0.512).abs() < 1e-12);
+        assert_eq!(unit, "J/GiB");
+
+        let elements = record("PCG64", Throughput::Elements(1000), 2.0);
+        let (joules, unit) = joules_per_unit(&elements).expect("elements throughput");
+        assert!((joules - 2.0).abs() < 1e-12);
+        assert_eq!(unit, "J/Gelem");
+    }
+
+    #[test]
+    fn best_efficiency_keeps_lowest_per_function() {
+        let records = vec![
+            record("XXH3-64", Throughput::Bytes(16), 1.0),
+            record("XXH3-64", Throughput::Bytes(4096), 10.0),
+            record("SHA2-256", Throughput::Bytes(16), 2.0),
+        ];
+        let best = best_efficiency("non_cryptographic_hash", &records);
+        assert_eq!(best.len(), 2);
+        assert_eq!(best[0].function, "XXH3-64");
+        assert_eq!(best[0].parameter, "4 KiB");
+        assert_eq!(best[1].function, "SHA2-256");
+    }
+}
@@ -1,16 +1,38 @@
 use std::{env::consts, fs, io::Write, path::Path};
 
-use crate::util::{run_capture, slugify};
+use crate::{
+    build_config::BuildConfig,
+    cli::{CurrentHostOpts, HostIdValue},
+    hardware::HardwareSpec,
+    metadata::{Ini, SCHEMA_KEY, SCHEMA_VERSION},
+    placement::Placement,
+    preflight::PreflightReport,
+    util::{run_capture, slugify},
+    versions::LibraryVersions,
+};
 
 pub const ENV_METADATA_FILE: &str = "environment.ini";
-const ENV_METADATA_FILE_LEGACY: &str = "environment.meta";
+pub const ENV_METADATA_FILE_LEGACY: &str = "environment.meta";
 
 #[derive(Debug, Clone)]
 pub struct BenchmarkEnvironment {
     os: Option<String>,
     kernel_release: Option<String>,
     cpu: Option<String>,
+    /// `std::env::consts::OS` of the host, e.g. `linux` or `macos`.
+    platform: Option<String>,
+    /// Hash of the OS machine id; tells apart machines with the same CPU.
+    machine: Option<String>,
+    /// Hypervisor the host runs under, or `none` on bare metal.
+    virtualization: Option<String>,
+    /// User label from `collect --label`.
+    label: Option<String>,
     compiler: CompilerSpec,
+    hardware: HardwareSpec,
+    build: Option<BuildConfig>,
+    versions: Option<LibraryVersions>,
+    preflight: Option<PreflightReport>,
+    placement: Option<Placement>,
 }
 
 impl BenchmarkEnvironment {
@@ -19,22 +41,134 @@ impl BenchmarkEnvironment {
             cpu: detect_cpu(),
             os: detect_os(),
             kernel_release: run_capture("uname", &["-sr"]),
+            platform: Some(consts::OS.to_owned()),
+            machine: detect_machine_id().map(|id| format!("{:016x}", fnv1a(id.trim().as_bytes()))),
+            virtualization: detect_virtualization(),
+            label: None,
             compiler: CompilerSpec::detect(),
+            hardware: HardwareSpec::detect(),
+            build: None,
+            versions: None,
+            preflight: None,
+            placement: None,
         }
     }
 
-    pub fn result_name(&self) -> String {
-        normalize_result_name(self.cpu())
+    /// Attach the `collect --label` of this host.
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Attach the build configuration of the benchmark binaries.
+    pub fn with_build(mut self, build: Option<BuildConfig>) -> Self {
+        self.build = build;
+        self
+    }
+
+    /// Attach the `Cargo.lock` versions of the benchmarked libraries.
+    pub fn with_versions(mut self, versions: Option<LibraryVersions>) -> Self {
+        self.versions = versions;
+        self
+    }
+
+    pub fn versions(&self) -> Option<&LibraryVersions> {
+        self.versions.as_ref()
+    }
+
+    /// Attach the CPU placement of the benchmark run these results come from.
+    pub fn with_placement(mut self, placement: Option<Placement>) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Attach the pre-flight checks of the benchmark run these results come from.
+    pub fn with_preflight(mut self, preflight: Option<PreflightReport>) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
+    /// Name `collect` gives the `results/` directory of the current machine.
+    pub fn current_result_name(opts: &CurrentHostOpts) -> String {
+        Self::detect()
+            .with_label(opts.label.clone())
+            .result_name(opts.host_id)
+    }
+
+    /// Name of the `results/` directory of this host under `scheme`, plus the label if any.
+    pub fn result_name(&self, scheme: HostIdValue) -> String {
+        let mut parts = vec![self.cpu()];
+        if scheme == HostIdValue::CpuOs {
+            parts.push(self.platform.as_deref().unwrap_or(consts::OS));
+        }
+        if let Some(label) = &self.label {
+            parts.push(label);
+        }
+        normalize_result_name(&parts.join(" "))
+    }
+
+    /// Why results recorded as `recorded` cannot come from this machine, if they cannot.
+    ///
+    /// Keys missing from `recorded` (older `environment.ini` files) are not compared.
+    pub fn machine_mismatch(&self, recorded: &Self) -> Option<String> {
+        if self.cpu() != recorded.cpu() {
+            return Some(format!(
+                "CPU `{}` instead of `{}`",
+                recorded.cpu(),
+                self.cpu()
+            ));
+        }
+        let differs = |ours: &Option<String>, theirs: &Option<String>| matches!((ours, theirs), (Some(ours), Some(theirs)) if ours != theirs);
+        if differs(&self.platform, &recorded.platform) {
+            return Some(format!(
+                "platform `{}` instead of `{}`",
+                recorded.platform.as_deref().unwrap_or_default(),
+                self.platform.as_deref().unwrap_or_default()
+            ));
+        }
+        if differs(&self.virtualization, &recorded.virtualization) {
+            return Some(format!(
+                "virtualization `{}` instead of `{}`",
+                recorded.virtualization.as_deref().unwrap_or_default(),
+                self.virtualization.as_deref().unwrap_or_default()
+            ));
+        }
+        if differs(&self.machine, &recorded.machine) {
+            return Some("a different machine id".to_owned());
+        }
+        None
     }
 
     pub fn write_markdown(&self, writer: &mut impl Write) -> std::io::Result<()> {
         writeln!(writer, "## Environment")?;
         writeln!(writer)?;
         writeln!(writer, "- CPU: {}", self.cpu())?;
+        if let Some(label) = &self.label {
+            writeln!(writer, "- Label: {label}")?;
+        }
+        self.hardware.write_markdown(writer)?;
         writeln!(writer, "- OS: {}", self.os())?;
         writeln!(writer, "- Kernel: {}", self.kernel_release())?;
+        if let Some(virtualization) = self.virtualization.as_deref().filter(|&v| v != "none") {
+            writeln!(writer, "- Virtualization: {virtualization}")?;
+        }
         writeln!(writer, "- rustc: {}", self.compiler.rustc)?;
         writeln!(writer, "- LLVM: {}", self.compiler.llvm)?;
+        if let Some(placement) = &self.placement {
+            placement.write_markdown(writer)?;
+        }
+        if let Some(build) = &self.build {
+            writeln!(writer)?;
+            build.write_markdown(writer)?;
+        }
+        if let Some(preflight) = &self.preflight {
+            writeln!(writer)?;
+            preflight.write_markdown(writer)?;
+        }
         Ok(())
     }
 
@@ -45,6 +179,7 @@ impl BenchmarkEnvironment {
     fn encode_ini(&self) -> String {
         let mut out = String::new();
         out.push_str("[environment]\n");
+        out.push_str(&format!("{SCHEMA_KEY} = {SCHEMA_VERSION}\n"));
         out.push_str(&format!("cpu = {}\n", encode_meta_value(self.cpu())));
         out.push_str(&format!("os = {}\n", encode_meta_value(self.os())));
         out.push_str(&format!(
@@ -59,57 +194,105 @@ impl BenchmarkEnvironment {
             "llvm = {}\n",
             encode_meta_value(&self.compiler.llvm)
         ));
+        for (key, value) in [
+            ("platform", &self.platform),
+            ("machine", &self.machine),
+            ("virtualization", &self.virtualization),
+            ("label", &self.label),
+        ] {
+            if let Some(value) = value {
+                out.push_str(&format!("{key} = {}\n", encode_meta_value(value)));
+            }
+        }
+        out.push('\n');
+        out.push_str(&self.hardware.encode_ini());
+        if let Some(build) = &self.build {
+            out.push('\n');
+            out.push_str(&build.encode_ini());
+        }
+        if let Some(versions) = &self.versions {
+            out.push('\n');
+            out.push_str(&versions.encode_ini());
+        }
+        if let Some(placement) = &self.placement {
+            out.push('\n');
+            out.push_str(&placement.encode_ini());
+        }
+        if let Some(preflight) = &self.preflight {
+            out.push('\n');
+            out.push_str(&preflight.encode_ini());
+        }
         out
     }
 
+    /// Decode a metadata file of any schema version, migrating it to the current one first.
     fn decode_ini(input: &str) -> Result<Self, String> {
-        let mut current_section: Option<&str> = None;
-        let mut cpu: Option<String> = None;
-        let mut os: Option<String> = None;
-        let mut kernel: Option<String> = None;
-        let mut rustc: Option<String> = None;
-        let mut llvm: Option<String> = None;
-
-        for raw_line in input.lines() {
-            let line = raw_line.trim();
-            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
-                continue;
-            }
-            if line.starts_with('[') && line.ends_with(']') {
-                current_section = Some(&line[1..line.len() - 1]);
-                continue;
-            }
-            if let Some(section) = current_section
-                && section != "environment"
-            {
-                continue;
-            }
-
-            let (key, value) = line
-                .split_once('=')
-                .ok_or_else(|| format!("invalid metadata line: {line}"))?;
-            let key = key.trim();
-            let value = decode_meta_value(value.trim());
-            match key {
-                "cpu" => cpu = Some(value),
-                "os" => os = Some(value),
-                "kernel" => kernel = Some(value),
-                "rustc" => rustc = Some(value),
-                "llvm" => llvm = Some(value),
-                _ => {}
-            }
-        }
+        Self::from_ini(&Ini::parse(input)?.migrate()?.ini)
+    }
 
-        let cpu = cpu.ok_or_else(|| "missing metadata key: cpu".to_owned())?;
-        Ok(Self {
-            os: option_if_non_empty(os),
-            kernel_release: option_if_non_empty(kernel),
-            cpu: option_if_non_empty(Some(cpu)),
+    /// Decode a file of the current schema, rejecting sections, keys and values that the
+    /// section encoders do not write.
+    pub fn from_ini(ini: &Ini) -> Result<Self, String> {
+        ini.validate()?;
+
+        let mut environment = Self {
+            os: None,
+            kernel_release: None,
+            cpu: None,
+            platform: None,
+            machine: None,
+            virtualization: None,
+            label: None,
             compiler: CompilerSpec {
-                rustc: rustc.unwrap_or_else(|| "not found".to_owned()),
-                llvm: llvm.unwrap_or_else(|| "not found".to_owned()),
+                rustc: "not found".to_owned(),
+                llvm: "not found".to_owned(),
             },
-        })
+            hardware: HardwareSpec::default(),
+            build: None,
+            versions: None,
+            preflight: None,
+            placement: None,
+        };
+        for (section, entries) in &ini.sections {
+            match section.as_str() {
+                "environment" => {
+                    for (key, value) in entries {
+                        let value = decode_meta_value(value);
+                        let field = match key.as_str() {
+                            SCHEMA_KEY => continue,
+                            "rustc" => {
+                                environment.compiler.rustc = value;
+                                continue;
+                            }
+                            "llvm" => {
+                                environment.compiler.llvm = value;
+                                continue;
+                            }
+                            "os" => &mut environment.os,
+                            "kernel" => &mut environment.kernel_release,
+                            "cpu" => &mut environment.cpu,
+                            "platform" => &mut environment.platform,
+                            "machine" => &mut environment.machine,
+                            "virtualization" => &mut environment.virtualization,
+                            "label" => &mut environment.label,
+                            _ => return Err(format!("unknown key `{key}` in [environment]")),
+                        };
+                        *field = option_if_non_empty(Some(value));
+                    }
+                }
+                "hardware" => environment.hardware = HardwareSpec::decode_section(entries)?,
+                "build" => environment.build = Some(BuildConfig::decode_section(entries)?),
+                "placement" => environment.placement = Some(Placement::decode_section(entries)?),
+                "preflight" => {
+                    environment.preflight = Some(PreflightReport::decode_section(entries)?);
+                }
+                _ => environment
+                    .versions
+                    .get_or_insert_with(LibraryVersions::default)
+                    .decode_section(section, entries)?,
+            }
+        }
+        Ok(environment)
     }
 
     fn os(&self) -> &str {
@@ -120,14 +303,48 @@ impl BenchmarkEnvironment {
         self.kernel_release.as_deref().unwrap_or("unknown")
     }
 
-    fn cpu(&self) -> &str {
+    /// OS the results were recorded on; `None` for files from before it was recorded.
+    pub fn platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    pub fn cpu(&self) -> &str {
         self.cpu.as_deref().unwrap_or(consts::ARCH)
     }
 }
 
+/// Environment of an `environment.ini` at any path, e.g. one sent along with results.
+pub fn read_metadata_file(path: &Path) -> Result<BenchmarkEnvironment, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {error}", path.display()))?;
+    BenchmarkEnvironment::decode_ini(&raw).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Environment recorded in a result directory, if any; invalid files are reported and skipped.
+pub fn read_metadata(result_dir: &Path) -> Option<BenchmarkEnvironment> {
+    try_read_metadata(result_dir)
+        .inspect_err(|error| {
+            eprintln!(
+                "warning: ignoring metadata of {}: {error}",
+                result_dir.display()
+            );
+        })
+        .ok()
+        .flatten()
+}
+
+/// Environment recorded in a result directory; `None` when there is no metadata file.
+pub fn try_read_metadata(result_dir: &Path) -> Result<Option<BenchmarkEnvironment>, String> {
+    [ENV_METADATA_FILE, ENV_METADATA_FILE_LEGACY]
+        .into_iter()
+        .map(|file| result_dir.join(file))
+        .find(|path| path.is_file())
+        .map(|path| read_metadata_file(&path))
+        .transpose()
+}
+
 pub fn read_cpu_from_metadata(result_dir: &Path) -> Option<String> {
-    let raw = read_metadata_raw(result_dir)?;
-    let environment = BenchmarkEnvironment::decode_ini(&raw).ok()?;
+    let environment = read_metadata(result_dir)?;
     let cpu = environment.cpu().trim();
     if cpu.is_empty() {
         return None;
@@ -135,6 +352,26 @@ pub fn read_cpu_from_metadata(result_dir: &Path) -> Option<String> {
     Some(cpu.to_owned())
 }
 
+/// Library versions recorded in a host result directory, if any.
+pub fn read_versions_from_metadata(result_dir: &Path) -> Option<LibraryVersions> {
+    read_metadata(result_dir)?.versions
+}
+
+/// The recorded `environment.ini` migrated to the current schema, with `[environment]`
+/// values unescaped, for commands that pass its sections on as they are.
+pub fn read_metadata_ini(result_dir: &Path) -> Option<Ini> {
+    let raw = read_metadata_raw(result_dir)?;
+    let mut ini = Ini::parse(&raw).and_then(Ini::migrate).ok()?.ini;
+    for (name, entries) in &mut ini.sections {
+        if name == "environment" {
+            for (_, value) in entries {
+                *value = decode_meta_value(value);
+            }
+        }
+    }
+    Some(ini)
+}
+
 fn read_metadata_raw(result_dir: &Path) -> Option<String> {
     let new_path = result_dir.join(ENV_METADATA_FILE);
     if new_path.is_file() {
@@ -219,6 +456,71 @@ fn normalize_result_name(cpu: &str) -> String {
     slugify(cpu)
 }
 
+/// Persistent per-installation id; only its hash is recorded.
+fn detect_machine_id() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        for path in ["/etc/machine-id", "/var/lib/dbus/machine-id"] {
+            if let Ok(id) = fs::read_to_string(path)
+                && !id.trim().is_empty()
+            {
+                return Some(id);
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(out) = run_capture("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"]) {
+            for line in out.lines() {
+                if let Some((key, value)) = line.split_once('=')
+                    && key.trim() == "\"IOPlatformUUID\""
+                {
+                    return Some(value.trim().trim_matches('"').to_owned());
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn detect_virtualization() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        // Prints `none` on bare metal and exits non-zero, so read stdout regardless.
+        if let Ok(output) = std::process::Command::new("systemd-detect-virt").output()
+            && let Ok(virt) = String::from_utf8(output.stdout)
+            && !virt.trim().is_empty()
+        {
+            return Some(virt.trim().to_owned());
+        }
+        if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+            let hypervisor = cpuinfo
+                .lines()
+                .filter(|line| line.starts_with("flags"))
+                .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"));
+            return Some(if hypervisor { "vm" } else { "none" }.to_owned());
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(present) = run_capture("sysctl", &["-n", "kern.hv_vmm_present"]) {
+            return Some(if present == "1" { "vm" } else { "none" }.to_owned());
+        }
+    }
+
+    None
+}
+
+/// 64-bit FNV-1a; stable across Rust releases, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
 fn option_if_non_empty(value: Option<String>) -> Option<String> {
     match value {
         Some(value) if !value.trim().is_empty() => Some(value),
@@ -271,9 +573,10 @@ fn detect_os() -> Option<String> {
 }
 
 #[derive(Debug, Clone)]
-struct CompilerSpec {
-    rustc: String,
-    llvm: String,
+pub struct CompilerSpec {
+    /// `rustc` version line, e.g. `rustc 1.93.1 (01f6ddf75 2026-02-11)`.
+    pub rustc: String,
+    pub llvm: String,
 }
 
 impl CompilerSpec {
@@ -286,20 +589,31 @@ impl CompilerSpec {
 
     fn detect() -> Self {
         let mut spec = Self::unknown();
-
         if let Some(rustc_vv) = run_capture("rustc", &["-Vv"]) {
-            for line in rustc_vv.lines() {
-                if line.starts_with("rustc ") {
-                    spec.rustc = line.to_owned();
-                }
-                if let Some(value) = line.strip_prefix("LLVM version:") {
-                    spec.llvm = value.trim().to_owned();
-                }
-            }
+            spec.decode_rustc_vv(&rustc_vv);
         }
-
         spec
     }
+
+    /// Versions of the rustup toolchain `toolchain`, e.g. `beta` or `1.89`; `None` when it
+    /// is not installed.
+    pub fn detect_toolchain(toolchain: &str) -> Option<Self> {
+        let rustc_vv = run_capture("rustup", &["run", toolchain, "rustc", "-Vv"])?;
+        let mut spec = Self::unknown();
+        spec.decode_rustc_vv(&rustc_vv);
+        Some(spec)
+    }
+
+    fn decode_rustc_vv(&mut self, rustc_vv: &str) {
+        for line in rustc_vv.lines() {
+            if line.starts_with("rustc ") {
+                self.rustc = line.to_owned();
+            }
+            if let Some(value) = line.strip_prefix("LLVM version:") {
+                self.llvm = value.trim().to_owned();
+            }
+        }
+    }
 }
 
 fn encode_meta_value(value: &str) -> String {
@@ -343,7 +657,15 @@ fn decode_meta_value(value: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{BenchmarkEnvironment, decode_meta_value, encode_meta_value};
+    use super::{BenchmarkEnvironment, decode_meta_value, encode_meta_value, fnv1a};
+    use crate::{
+        build_config::BuildConfig,
+        cli::HostIdValue,
+        hardware::HardwareSpec,
+        placement::Placement,
+        preflight::{Check, PreflightReport},
+        versions::LibraryVersions,
+    };
 
     #[test]
     fn metadata_roundtrip() {
@@ -351,10 +673,55 @@ mod tests {
             os: Some("macOS 26.3 (25D125)".to_owned()),
             kernel_release: Some("Darwin 25.3.0".to_owned()),
             cpu: Some("Apple M1".to_owned()),
+            platform: Some("macos".to_owned()),
+            machine: Some("0123456789abcdef".to_owned()),
+            virtualization: Some("none".to_owned()),
+            label: Some("desk".to_owned()),
             compiler: super::CompilerSpec {
                 rustc: "rustc 1.93.1".to_owned(),
                 llvm: "21.1.8".to_owned(),
             },
+            hardware: HardwareSpec {
+                l1d_bytes: Some(128 << 10),
+                l1i_bytes: Some(192 << 10),
+                l2_bytes: Some(12 << 20),
+                l3_bytes: Some(16 << 20),
+                cores: Some(8),
+                threads: Some(8),
+                base_mhz: Some(600),
+                max_mhz: Some(3204),
+                memory_bytes: Some(16 << 30),
+                memory_speed: Some("LPDDR4X 4266 MT/s".to_owned()),
+                isa: vec!["NEON".to_owned(), "AES".to_owned()],
+            },
+            build: Some(BuildConfig {
+                rustflags: vec!["-Ctarget-cpu=native".to_owned()],
+                target: Some("aarch64-apple-darwin".to_owned()),
+                target_features: vec!["neon".to_owned(), "aes".to_owned()],
+                profile: vec![("lto".to_owned(), "\"fat\"".to_owned())],
+            }),
+            versions: Some({
+                let mut versions = LibraryVersions::default();
+                versions
+                    .decode_section("dependencies.hash", &[(
+                        "gxhash".to_owned(),
+                        "3.5.0".to_owned(),
+                    )])
+                    .expect("decode versions");
+                versions
+            }),
+            preflight: Some(PreflightReport {
+                checks: vec![Check {
+                    key: "governor",
+                    value: "powersave".to_owned(),
+                    warning: Some("frequency scaling distorts timings".to_owned()),
+                }],
+            }),
+            placement: Some(Placement {
+                cpus: Some(vec![2, 3]),
+                isolated: true,
+                nice: Some(-5),
+            }),
         };
         let encoded = env.encode_ini();
         let decoded = BenchmarkEnvironment::decode_ini(&encoded).expect("decode should succeed");
@@ -363,6 +730,58 @@ mod tests {
         assert_eq!(decoded.kernel_release, Some("Darwin 25.3.0".to_owned()));
         assert_eq!(decoded.compiler.rustc, "rustc 1.93.1");
         assert_eq!(decoded.compiler.llvm, "21.1.8");
+        assert_eq!(decoded.platform, env.platform);
+        assert_eq!(decoded.machine, env.machine);
+        assert_eq!(decoded.virtualization, env.virtualization);
+        assert_eq!(decoded.label(), Some("desk"));
+        assert_eq!(decoded.hardware, env.hardware);
+        assert_eq!(decoded.build, env.build);
+        assert_eq!(decoded.versions, env.versions);
+        assert_eq!(decoded.preflight, env.preflight);
+        assert_eq!(decoded.placement, env.placement);
+    }
+
+    #[test]
+    fn result_name_schemes_and_machine_mismatch() {
+        let recorded = BenchmarkEnvironment::decode_ini(
+            "[environment]\ncpu = Apple M4\nplatform = macos\nmachine = aaaa\nvirtualization = none\n",
+        )
+        .expect("decode");
+        assert_eq!(recorded.result_name(HostIdValue::Cpu), "apple-m4");
+        assert_eq!(recorded.result_name(HostIdValue::CpuOs), "apple-m4-macos");
+        let labeled = recorded.clone().with_label(Some("Studio Desk".to_owned()));
+        assert_eq!(
+            labeled.result_name(HostIdValue::CpuOs),
+            "apple-m4-macos-studio-desk"
+        );
+
+        assert_eq!(recorded.machine_mismatch(&recorded), None);
+        let legacy = BenchmarkEnvironment::decode_ini("cpu = Apple M4\n").expect("decode");
+        assert_eq!(recorded.machine_mismatch(&legacy), None);
+
+        let mut other = recorded.clone();
+        other.machine = Some("bbbb".to_owned());
+        assert_eq!(
+            recorded.machine_mismatch(&other).as_deref(),
+            Some("a different machine id")
+        );
+        other.virtualization = Some("kvm".to_owned());
+        assert_eq!(
+            recorded.machine_mismatch(&other).as_deref(),
+            Some("virtualization `kvm` instead of `none`")
+        );
+        other.cpu = Some("Apple M1".to_owned());
+        assert!(
+            recorded
+                .machine_mismatch(&other)
+                .is_some_and(|why| why.starts_with("CPU"))
+        );
+    }
+
+    #[test]
+    fn fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
     }
 
     #[test]
@@ -0,0 +1,315 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    cli::{ExportFormat, ExportOpts},
+    criterion::Throughput,
+    environment::read_metadata_ini,
+    runs::{SUMMARY_FILE, latest_run_dir, list_runs, read_summary, run_timestamp},
+    util::{Result, workspace_root},
+};
+
+/// Columns of the exported dataset, in output order; the fields of [`ExportRow`].
+const COLUMNS: &[&str] = &[
+    "host",
+    "run",
+    "timestamp",
+    "cpu",
+    "os",
+    "kernel",
+    "rustc",
+    "llvm",
+    "platform",
+    "virtualization",
+    "label",
+    "target",
+    "rustflags",
+    "scope",
+    "group",
+    "function",
+    "parameter",
+    "unit",
+    "amount",
+    "throughput",
+    "ci_low",
+    "ci_high",
+    "mean_ns",
+];
+
+/// One benchmark case of one run: a row of the tidy dataset.
+#[derive(Debug, Serialize)]
+struct ExportRow {
+    /// Directory under `results/`.
+    host: String,
+    run: String,
+    /// Collection time, RFC 3339 in UTC.
+    timestamp: Option<String>,
+    cpu: Option<String>,
+    os: Option<String>,
+    kernel: Option<String>,
+    rustc: Option<String>,
+    llvm: Option<String>,
+    platform: Option<String>,
+    virtualization: Option<String>,
+    label: Option<String>,
+    target: Option<String>,
+    rustflags: Option<String>,
+    scope: String,
+    group: String,
+    function: String,
+    parameter: Option<String>,
+    /// `bytes`, `elements` or `bits` per iteration.
+    unit: Option<&'static str>,
+    amount: Option<u64>,
+    /// Units per second at the mean, and at the bounds of its confidence interval.
+    throughput: Option<f64>,
+    ci_low: Option<f64>,
+    ci_high: Option<f64>,
+    mean_ns: f64,
+}
+
+pub fn export_results(opts: &ExportOpts) -> Result<()> {
+    let rows = collect_rows(&workspace_root().join("results"), opts.latest)?;
+    match &opts.out {
+        Some(path) => {
+            let mut file = io::BufWriter::new(fs::File::create(path)?);
+            write_rows(&mut file, &rows, opts.format)?;
+            file.flush()?;
+            eprintln!("wrote {} row(s) to {}", rows.len(), path.display());
+        }
+        None => write_rows(&mut io::stdout().lock(), &rows, opts.format)?,
+    }
+    Ok(())
+}
+
+/// Rows of every run (or only the published one) of every host in `results_root`.
+fn collect_rows(results_root: &Path, latest_only: bool) -> Result<Vec<ExportRow>> {
+    if !results_root.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut host_dirs = Vec::new();
+    for entry in fs::read_dir(results_root)? {
+        let entry = entry?;
+        if entry.path().is_dir() {
+            host_dirs.push((
+                entry.file_name().to_string_lossy().into_owned(),
+                entry.path(),
+            ));
+        }
+    }
+    host_dirs.sort();
+
+    let mut rows = Vec::new();
+    let mut skipped = 0;
+    for (host, host_dir) in host_dirs {
+        let mut runs = list_runs(&host_dir)?;
+        if latest_only || runs.is_empty() {
+            let dir = latest_run_dir(&host_dir);
+            // Hosts collected before runs were kept have no run id.
+            let id = match dir.file_name() {
+                Some(name) if dir != host_dir => name.to_string_lossy().into_owned(),
+                _ => String::new(),
+            };
+            runs = vec![(id, dir)];
+        }
+        for (run, run_dir) in runs {
+            let Some(entries) = read_summary(&run_dir)? else {
+                skipped += 1;
+                continue;
+            };
+            let metadata = read_metadata_ini(&run_dir).unwrap_or_default();
+            let field = |section: &str, key: &str| metadata.get(section, key).map(str::to_owned);
+            for entry in entries {
+                let record = entry.record;
+                let rate_at = |ns: f64| {
+                    let amount = record.throughput?.amount() as f64;
+                    (ns > 0.0).then(|| amount * 1e9 / ns)
+                };
+                rows.push(ExportRow {
+                    host: host.clone(),
+                    run: run.clone(),
+                    timestamp: run_timestamp(&run),
+                    cpu: field("environment", "cpu"),
+                    os: field("environment", "os"),
+                    kernel: field("environment", "kernel"),
+                    rustc: field("environment", "rustc"),
+                    llvm: field("environment", "llvm"),
+                    platform: field("environment", "platform"),
+                    virtualization: field("environment", "virtualization"),
+                    label: field("environment", "label"),
+                    target: field("build", "target"),
+                    rustflags: field("build", "rustflags"),
+                    scope: entry.scope,
+                    group: entry.group,
+                    unit: record.throughput.map(|throughput| match throughput {
+                        Throughput::Bytes(_) | Throughput::BytesDecimal(_) => "bytes",
+                        Throughput::Elements(_) => "elements",
+                        Throughput::Bits(_) => "bits",
+                    }),
+                    amount: record.throughput.map(Throughput::amount),
+                    throughput: record.throughput_per_sec(),
+                    // The slowest mean time bounds the throughput from below.
+                    ci_low: rate_at(record.mean.upper),
+                    ci_high: rate_at(record.mean.lower),
+                    mean_ns: record.mean.point,
+                    parameter: record.parameter,
+                    function: record.function,
+                });
+            }
+        }
+    }
+    if skipped > 0 {
+        eprintln!("note: skipped {skipped} run(s) without {SUMMARY_FILE}");
+    }
+    Ok(rows)
+}
+
+fn write_rows(writer: &mut impl Write, rows: &[ExportRow], format: ExportFormat) -> Result<()> {
+    let encode_error = |error: serde_json::Error| format!("failed to encode export: {error}");
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, rows).map_err(encode_error)?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Ndjson => {
+            for row in rows {
+                serde_json::to_writer(&mut *writer, row).map_err(encode_error)?;
+                writeln!(writer)?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(writer, "{}", COLUMNS.join(","))?;
+            for row in rows {
+                let value = serde_json::to_value(row).map_err(encode_error)?;
+                let cells: Vec<String> = COLUMNS
+                    .iter()
+                    .map(|&column| match &value[column] {
+                        serde_json::Value::Null => String::new(),
+                        serde_json::Value::String(text) => csv_field(text),
+                        other => other.to_string(),
+                    })
+                    .collect();
+                writeln!(writer, "{}", cells.join(","))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// RFC 4180 quoting for fields holding separators, quotes or line breaks.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{COLUMNS, collect_rows, csv_field, write_rows};
+    use crate::{cli::ExportFormat, criterion::tests::write_case, runs::write_summary};
+
+    #[test]
+    fn export_formats() {
+        let root = temp_dir("export");
+        let host_dir = root.join("results/apple-m4-macos");
+        let criterion = root.join("criterion");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            50.0,
+        );
+        for run in ["2026-03-01T08-44-54Z", "2026-03-02T09-00-00Z"] {
+            let run_dir = host_dir.join("runs").join(run);
+            fs::create_dir_all(&run_dir).expect("create run dir");
+            fs::write(
+                run_dir.join("environment.ini"),
+                "[environment]\ncpu = Apple M4, 10 cores\nos = macOS\n\n[build]\ntarget = aarch64-apple-darwin\n",
+            )
+            .expect("write meta");
+            write_summary(&run_dir, &criterion).expect("write summary");
+        }
+        fs::write(host_dir.join("latest"), "2026-03-02T09-00-00Z\n").expect("write latest");
+
+        let rows = collect_rows(&root.join("results"), false).expect("collect rows");
+        assert_eq!(rows.len(), 2);
+        let row = &rows[0];
+        assert_eq!(row.timestamp.as_deref(), Some("2026-03-01T08:44:54Z"));
+        assert_eq!(row.cpu.as_deref(), Some("Apple M4, 10 cores"));
+        assert_eq!(row.target.as_deref(), Some("aarch64-apple-darwin"));
+        assert_eq!(row.unit, Some("bytes"));
+        let throughput = row.throughput.expect("throughput");
+        assert!(row.ci_low.expect("ci_low") < throughput);
+        assert!(row.ci_high.expect("ci_high") > throughput);
+
+        let latest = collect_rows(&root.join("results"), true).expect("collect latest");
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].run, "2026-03-02T09-00-00Z");
+
+        let value = serde_json::to_value(row).expect("encode row");
+        let fields: Vec<&str> = value
+            .as_object()
+            .expect("row object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut columns = COLUMNS.to_vec();
+        columns.sort_unstable();
+        assert_eq!(fields, columns);
+
+        let mut csv = Vec::new();
+        write_rows(&mut csv, &rows, ExportFormat::Csv).expect("write csv");
+        let csv = String::from_utf8(csv).expect("utf-8");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("host,run,timestamp,cpu,"));
+        assert!(lines[1].starts_with(
+            "apple-m4-macos,2026-03-01T08-44-54Z,2026-03-01T08:44:54Z,\"Apple M4, 10 cores\",macOS,"
+        ));
+
+        let mut ndjson = Vec::new();
+        write_rows(&mut ndjson, &rows, ExportFormat::Ndjson).expect("write ndjson");
+        let ndjson = String::from_utf8(ndjson).expect("utf-8");
+        assert_eq!(ndjson.lines().count(), 2);
+        for line in ndjson.lines() {
+            let row: serde_json::Value = serde_json::from_str(line).expect("parse line");
+            assert_eq!(row["function"], "XXH3-64");
+        }
+
+        let mut json = Vec::new();
+        write_rows(&mut json, &rows, ExportFormat::Json).expect("write json");
+        let json: serde_json::Value = serde_json::from_slice(&json).expect("parse json");
+        assert_eq!(json.as_array().map(Vec::len), Some(2));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("say \"hi\", twice"), "\"say \"\"hi\"\", twice\"");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
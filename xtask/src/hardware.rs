@@ -0,0 +1,446 @@
+use std::{collections::BTreeSet, fs, io::Write, path::Path};
+
+use crate::util::{format_bytes, run_capture};
+
+/// ISA extensions relevant to the benchmarked algorithms, as `/proc/cpuinfo` flag and label.
+const X86_FEATURES: &[(&str, &str)] = &[
+    ("sse2", "SSE2"),
+    ("sse4_2", "SSE4.2"),
+    ("avx", "AVX"),
+    ("avx2", "AVX2"),
+    ("bmi2", "BMI2"),
+    ("avx512f", "AVX-512F"),
+    ("avx512bw", "AVX-512BW"),
+    ("avx512vl", "AVX-512VL"),
+    ("aes", "AES-NI"),
+    ("vaes", "VAES"),
+    ("pclmulqdq", "PCLMULQDQ"),
+    ("sha_ni", "SHA-NI"),
+];
+
+/// Same for the `Features` line of ARM `/proc/cpuinfo`.
+const ARM_FEATURES: &[(&str, &str)] = &[
+    ("asimd", "NEON"),
+    ("sve", "SVE"),
+    ("sve2", "SVE2"),
+    ("aes", "AES"),
+    ("pmull", "PMULL"),
+    ("sha2", "SHA2"),
+    ("sha512", "SHA512"),
+    ("sha3", "SHA3"),
+];
+
+/// Hardware details that put throughput numbers in context.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HardwareSpec {
+    pub l1d_bytes: Option<u64>,
+    pub l1i_bytes: Option<u64>,
+    pub l2_bytes: Option<u64>,
+    pub l3_bytes: Option<u64>,
+    pub cores: Option<usize>,
+    pub threads: Option<usize>,
+    pub base_mhz: Option<u64>,
+    pub max_mhz: Option<u64>,
+    pub memory_bytes: Option<u64>,
+    /// Module type and configured speed, e.g. `DDR5 5600 MT/s`.
+    pub memory_speed: Option<String>,
+    /// Labels from [`X86_FEATURES`] / [`ARM_FEATURES`] the CPU supports.
+    pub isa: Vec<String>,
+}
+
+impl HardwareSpec {
+    pub fn detect() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            let mut spec = Self::detect_linux(Path::new("/"));
+            spec.memory_speed = run_capture("dmidecode", &["-t", "memory"])
+                .and_then(|out| parse_dmidecode_memory(&out));
+            spec
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            Self::detect_macos()
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Read `/sys` and `/proc` below `root`, as seen by CPU 0.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn detect_linux(root: &Path) -> Self {
+        let cpu_dir = root.join("sys/devices/system/cpu");
+        let mut spec = Self::default();
+
+        if let Ok(entries) = fs::read_dir(cpu_dir.join("cpu0/cache")) {
+            for entry in entries.flatten() {
+                let dir = entry.path();
+                let read = |name: &str| read_trimmed(&dir.join(name));
+                let Some(size) = read("size").and_then(|size| parse_cache_size(&size)) else {
+                    continue;
+                };
+                let slot = match (read("level").as_deref(), read("type").as_deref()) {
+                    (Some("1"), Some("Data")) => &mut spec.l1d_bytes,
+                    (Some("1"), Some("Instruction")) => &mut spec.l1i_bytes,
+                    (Some("2"), _) => &mut spec.l2_bytes,
+                    (Some("3"), _) => &mut spec.l3_bytes,
+                    _ => continue,
+                };
+                *slot = Some(size);
+            }
+        }
+
+        let mut cores = BTreeSet::new();
+        let mut threads = 0;
+        if let Ok(entries) = fs::read_dir(&cpu_dir) {
+            for entry in entries.flatten() {
+                let topology = entry.path().join("topology");
+                let package = read_trimmed(&topology.join("physical_package_id"));
+                let core = read_trimmed(&topology.join("core_id"));
+                if let (Some(package), Some(core)) = (package, core) {
+                    cores.insert((package, core));
+                    threads += 1;
+                }
+            }
+        }
+        if threads > 0 {
+            spec.cores = Some(cores.len());
+            spec.threads = Some(threads);
+        }
+
+        let khz = |name: &str| {
+            read_trimmed(&cpu_dir.join("cpu0/cpufreq").join(name))?
+                .parse::<u64>()
+                .ok()
+                .map(|khz| khz / 1000)
+        };
+        spec.base_mhz = khz("base_frequency");
+        spec.max_mhz = khz("cpuinfo_max_freq");
+
+        spec.memory_bytes = read_trimmed(&root.join("proc/meminfo"))
+            .and_then(|meminfo| parse_meminfo_total(&meminfo));
+        spec.isa = fs::read_to_string(root.join("proc/cpuinfo"))
+            .map(|cpuinfo| parse_cpuinfo_isa(&cpuinfo))
+            .unwrap_or_default();
+        spec
+    }
+
+    #[cfg(target_os = "macos")]
+    fn detect_macos() -> Self {
+        let sysctl = |key: &str| {
+            run_capture("sysctl", &["-n", key]).and_then(|value| value.parse::<u64>().ok())
+        };
+        let mut isa = Vec::new();
+        for (key, label) in [
+            ("hw.optional.neon", "NEON"),
+            ("hw.optional.arm.FEAT_AES", "AES"),
+            ("hw.optional.arm.FEAT_PMULL", "PMULL"),
+            ("hw.optional.arm.FEAT_SHA256", "SHA2"),
+            ("hw.optional.arm.FEAT_SHA512", "SHA512"),
+            ("hw.optional.arm.FEAT_SHA3", "SHA3"),
+            ("hw.optional.sse2", "SSE2"),
+            ("hw.optional.sse4_2", "SSE4.2"),
+            ("hw.optional.avx1_0", "AVX"),
+            ("hw.optional.avx2_0", "AVX2"),
+            ("hw.optional.avx512f", "AVX-512F"),
+            ("hw.optional.aes", "AES-NI"),
+        ] {
+            if sysctl(key) == Some(1) {
+                isa.push(label.to_owned());
+            }
+        }
+        Self {
+            l1d_bytes: sysctl("hw.l1dcachesize"),
+            l1i_bytes: sysctl("hw.l1icachesize"),
+            l2_bytes: sysctl("hw.l2cachesize"),
+            l3_bytes: sysctl("hw.l3cachesize").filter(|size| *size > 0),
+            cores: sysctl("hw.physicalcpu").map(|n| n as usize),
+            threads: sysctl("hw.logicalcpu").map(|n| n as usize),
+            base_mhz: sysctl("hw.cpufrequency").map(|hz| hz / 1_000_000),
+            max_mhz: sysctl("hw.cpufrequency_max").map(|hz| hz / 1_000_000),
+            memory_bytes: sysctl("hw.memsize"),
+            memory_speed: None,
+            isa,
+        }
+    }
+
+    /// `[hardware]` section of `environment.ini`; unknown values are omitted.
+    pub fn encode_ini(&self) -> String {
+        let mut out = String::from("[hardware]\n");
+        for (key, value) in self.entries() {
+            out.push_str(&format!("{key} = {value}\n"));
+        }
+        out
+    }
+
+    /// Decode the `[hardware]` section written by [`Self::encode_ini`].
+    pub fn decode_section(entries: &[(String, String)]) -> Result<Self, String> {
+        let mut spec = Self::default();
+        for (key, value) in entries {
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map(Some)
+                    .map_err(|_| format!("[hardware] {key}: `{value}` is not a number"))
+            };
+            match key.as_str() {
+                "l1d_bytes" => spec.l1d_bytes = number()?,
+                "l1i_bytes" => spec.l1i_bytes = number()?,
+                "l2_bytes" => spec.l2_bytes = number()?,
+                "l3_bytes" => spec.l3_bytes = number()?,
+                "cores" => spec.cores = number()?.map(|n| n as usize),
+                "threads" => spec.threads = number()?.map(|n| n as usize),
+                "base_mhz" => spec.base_mhz = number()?,
+                "max_mhz" => spec.max_mhz = number()?,
+                "memory_bytes" => spec.memory_bytes = number()?,
+                "memory_speed" => spec.memory_speed = Some(value.to_owned()),
+                "isa" => {
+                    spec.isa = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|label| !label.is_empty())
+                        .map(str::to_owned)
+                        .collect();
+                }
+                _ => return Err(format!("unknown key `{key}` in [hardware]")),
+            }
+        }
+        Ok(spec)
+    }
+
+    fn entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = Vec::new();
+        let mut push = |key, value: Option<String>| {
+            if let Some(value) = value {
+                entries.push((key, value));
+            }
+        };
+        push("l1d_bytes", self.l1d_bytes.map(|v| v.to_string()));
+        push("l1i_bytes", self.l1i_bytes.map(|v| v.to_string()));
+        push("l2_bytes", self.l2_bytes.map(|v| v.to_string()));
+        push("l3_bytes", self.l3_bytes.map(|v| v.to_string()));
+        push("cores", self.cores.map(|v| v.to_string()));
+        push("threads", self.threads.map(|v| v.to_string()));
+        push("base_mhz", self.base_mhz.map(|v| v.to_string()));
+        push("max_mhz", self.max_mhz.map(|v| v.to_string()));
+        push("memory_bytes", self.memory_bytes.map(|v| v.to_string()));
+        push("memory_speed", self.memory_speed.clone());
+        push("isa", (!self.isa.is_empty()).then(|| self.isa.join(",")));
+        entries
+    }
+
+    pub fn write_markdown(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let unknown = || "unknown".to_owned();
+        let caches = [
+            ("L1d", self.l1d_bytes),
+            ("L1i", self.l1i_bytes),
+            ("L2", self.l2_bytes),
+            ("L3", self.l3_bytes),
+        ]
+        .into_iter()
+        .filter_map(|(name, size)| Some(format!("{name} {}", format_bytes(size?))))
+        .collect::<Vec<_>>();
+        if !caches.is_empty() {
+            writeln!(writer, "- Caches (per CPU 0): {}", caches.join(", "))?;
+        }
+        if self.cores.is_some() || self.threads.is_some() {
+            writeln!(
+                writer,
+                "- Cores / threads: {} / {}",
+                self.cores.map_or_else(unknown, |n| n.to_string()),
+                self.threads.map_or_else(unknown, |n| n.to_string())
+            )?;
+        }
+        if self.base_mhz.is_some() || self.max_mhz.is_some() {
+            writeln!(
+                writer,
+                "- Frequency (base / max): {} / {}",
+                self.base_mhz
+                    .map_or_else(unknown, |mhz| format!("{mhz} MHz")),
+                self.max_mhz
+                    .map_or_else(unknown, |mhz| format!("{mhz} MHz"))
+            )?;
+        }
+        if let Some(bytes) = self.memory_bytes {
+            let gib = bytes as f64 / (1u64 << 30) as f64;
+            match &self.memory_speed {
+                Some(speed) => writeln!(writer, "- Memory: {gib:.1} GiB, {speed}")?,
+                None => writeln!(writer, "- Memory: {gib:.1} GiB")?,
+            }
+        }
+        if !self.isa.is_empty() {
+            writeln!(writer, "- ISA extensions: {}", self.isa.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(fs::read_to_string(path).ok()?.trim().to_owned())
+}
+
+/// Parse sysfs cache sizes such as `48K` or `16M`.
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (digits, factor) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 1 << 10),
+        b'M' => (&size[..size.len() - 1], 1 << 20),
+        b'G' => (&size[..size.len() - 1], 1 << 30),
+        _ => (size, 1),
+    };
+    Some(digits.parse::<u64>().ok()? * factor)
+}
+
+fn parse_meminfo_total(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+fn parse_cpuinfo_isa(cpuinfo: &str) -> Vec<String> {
+    for line in cpuinfo.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let table = match key.trim() {
+            "flags" => X86_FEATURES,
+            "Features" => ARM_FEATURES,
+            _ => continue,
+        };
+        let flags: BTreeSet<&str> = value.split_whitespace().collect();
+        return table
+            .iter()
+            .filter(|(flag, _)| flags.contains(flag))
+            .map(|(_, label)| (*label).to_owned())
+            .collect();
+    }
+    Vec::new()
+}
+
+/// First populated module of `dmidecode -t memory` (needs root), e.g. `DDR4 3200 MT/s`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_dmidecode_memory(output: &str) -> Option<String> {
+    for device in output.split("Memory Device").skip(1) {
+        let field = |name: &str| {
+            device.lines().find_map(|line| {
+                let value = line.trim().strip_prefix(name)?.strip_prefix(':')?.trim();
+                (!value.is_empty() && value != "Unknown").then_some(value)
+            })
+        };
+        let Some(speed) = field("Configured Memory Speed").or_else(|| field("Speed")) else {
+            continue;
+        };
+        return Some(match field("Type") {
+            Some(kind) => format!("{kind} {speed}"),
+            None => speed.to_owned(),
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{HardwareSpec, parse_cpuinfo_isa, parse_dmidecode_memory};
+    use crate::metadata::Ini;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(path, content).expect("write file");
+    }
+
+    #[test]
+    fn detect_linux_reads_sysfs_and_procfs() {
+        let root = temp_dir("hardware-linux");
+        let cpu = "sys/devices/system/cpu";
+        for (index, level, kind, size) in [
+            (0, "1", "Data", "48K"),
+            (1, "1", "Instruction", "32K"),
+            (2, "2", "Unified", "2048K"),
+            (3, "3", "Unified", "32M"),
+        ] {
+            let dir = format!("{cpu}/cpu0/cache/index{index}");
+            write(&root, &format!("{dir}/level"), level);
+            write(&root, &format!("{dir}/type"), kind);
+            write(&root, &format!("{dir}/size"), size);
+        }
+        for (n, core) in [(0, "0"), (1, "0"), (2, "1"), (3, "1")] {
+            write(
+                &root,
+                &format!("{cpu}/cpu{n}/topology/physical_package_id"),
+                "0",
+            );
+            write(&root, &format!("{cpu}/cpu{n}/topology/core_id"), core);
+        }
+        write(
+            &root,
+            &format!("{cpu}/cpu0/cpufreq/base_frequency"),
+            "3000000\n",
+        );
+        write(
+            &root,
+            &format!("{cpu}/cpu0/cpufreq/cpuinfo_max_freq"),
+            "5100000\n",
+        );
+        write(
+            &root,
+            "proc/meminfo",
+            "MemTotal:       16384000 kB\nMemFree: 1 kB\n",
+        );
+        write(
+            &root,
+            "proc/cpuinfo",
+            "processor\t: 0\nflags\t\t: sse2 avx2 aes sha_ni\n",
+        );
+
+        let spec = HardwareSpec::detect_linux(&root);
+        assert_eq!(spec.l1d_bytes, Some(48 << 10));
+        assert_eq!(spec.l1i_bytes, Some(32 << 10));
+        assert_eq!(spec.l2_bytes, Some(2 << 20));
+        assert_eq!(spec.l3_bytes, Some(32 << 20));
+        assert_eq!((spec.cores, spec.threads), (Some(2), Some(4)));
+        assert_eq!((spec.base_mhz, spec.max_mhz), (Some(3000), Some(5100)));
+        assert_eq!(spec.memory_bytes, Some(16_384_000 * 1024));
+        assert_eq!(spec.isa, ["SSE2", "AVX2", "AES-NI", "SHA-NI"]);
+
+        let ini = Ini::parse(&spec.encode_ini()).expect("parse");
+        let decoded = HardwareSpec::decode_section(ini.entries("hardware")).expect("decode");
+        assert_eq!(decoded, spec);
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn cpuinfo_isa_arm_features() {
+        let cpuinfo = "processor\t: 0\nFeatures\t: fp asimd aes pmull sha1 sha2 crc32\n";
+        assert_eq!(parse_cpuinfo_isa(cpuinfo), ["NEON", "AES", "PMULL", "SHA2"]);
+    }
+
+    #[test]
+    fn dmidecode_skips_empty_slots() {
+        let output = "Memory Device\n\tSize: No Module Installed\n\tType: Unknown\n\tSpeed: \
+                      Unknown\n\nMemory Device\n\tSize: 16 GB\n\tType: DDR5\n\tSpeed: 6000 \
+                      MT/s\n\tConfigured Memory Speed: 5600 MT/s\n";
+        assert_eq!(
+            parse_dmidecode_memory(output).as_deref(),
+            Some("DDR5 5600 MT/s")
+        );
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
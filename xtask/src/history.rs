@@ -0,0 +1,194 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    cli::HistoryOpts,
+    criterion::BenchmarkRecord,
+    environment::BenchmarkEnvironment,
+    runs::{SUMMARY_FILE, list_runs, read_summary},
+    scope::Scope,
+    util::{Result, workspace_root},
+};
+
+pub fn print_history(opts: &HistoryOpts) -> Result<()> {
+    let host = opts
+        .host
+        .clone()
+        .unwrap_or_else(|| BenchmarkEnvironment::current_result_name(&opts.current_host));
+    let host_dir = workspace_root().join("results").join(&host);
+    let runs = list_runs(&host_dir)?;
+    if runs.is_empty() {
+        return Err(format!("no runs collected for `{host}` in {}", host_dir.display()).into());
+    }
+    write_history(
+        &mut io::stdout().lock(),
+        &host,
+        &runs,
+        opts.scope.to_scopes(),
+        &opts.bench,
+    )
+}
+
+/// One markdown table per summary group: runs as rows, parameters as columns, each cell
+/// the throughput and its change against the previous run.
+fn write_history(
+    writer: &mut impl Write,
+    host: &str,
+    runs: &[(String, PathBuf)],
+    scopes: &[Scope],
+    bench: &str,
+) -> Result<()> {
+    // group -> (run id, records of `bench`), in run order.
+    let mut series: BTreeMap<&str, Vec<(&str, Vec<BenchmarkRecord>)>> = BTreeMap::new();
+    let mut skipped = 0;
+    for (run_id, run_dir) in runs {
+        let Some(entries) = read_summary(run_dir)? else {
+            skipped += 1;
+            continue;
+        };
+        for &scope in scopes {
+            for &group in scope.summary_groups() {
+                let records: Vec<BenchmarkRecord> = entries
+                    .iter()
+                    .filter(|entry| entry.scope == scope.slug() && entry.group == group)
+                    .filter(|entry| entry.record.function == bench)
+                    .map(|entry| entry.record.clone())
+                    .collect();
+                if !records.is_empty() {
+                    series.entry(group).or_default().push((run_id, records));
+                }
+            }
+        }
+    }
+    if skipped > 0 {
+        eprintln!("note: skipped {skipped} run(s) of `{host}` without {SUMMARY_FILE}");
+    }
+    if series.is_empty() {
+        return Err(format!("no results for `{bench}` in the runs of `{host}`").into());
+    }
+
+    writeln!(writer, "# `{bench}` on {host}")?;
+    for &scope in scopes {
+        for &group in scope.summary_groups() {
+            let Some(rows) = series.get(group) else {
+                continue;
+            };
+            let mut parameters: Vec<String> = Vec::new();
+            for record in rows.iter().flat_map(|(_, records)| records) {
+                let parameter = record.display_parameter();
+                if !parameters.contains(&parameter) {
+                    parameters.push(parameter);
+                }
+            }
+
+            writeln!(writer)?;
+            writeln!(writer, "## {group}")?;
+            writeln!(writer)?;
+            write!(writer, "| Run |")?;
+            for parameter in &parameters {
+                write!(writer, " {parameter} |")?;
+            }
+            writeln!(writer)?;
+            writeln!(writer, "| --- |{}", " --- |".repeat(parameters.len()))?;
+
+            let mut previous: BTreeMap<&str, f64> = BTreeMap::new();
+            for (run_id, records) in rows {
+                write!(writer, "| {run_id} |")?;
+                for parameter in &parameters {
+                    let record = records
+                        .iter()
+                        .find(|record| &record.display_parameter() == parameter);
+                    let cell = match record.and_then(|record| {
+                        Some((record.throughput_per_sec()?, record.display_throughput()?))
+                    }) {
+                        Some((rate, display)) => {
+                            let change = previous
+                                .insert(parameter, rate)
+                                .map(|before| format!(" ({:+.1}%)", (rate / before - 1.0) * 100.0))
+                                .unwrap_or_default();
+                            format!("{display}{change}")
+                        }
+                        None => "-".to_owned(),
+                    };
+                    write!(writer, " {cell} |")?;
+                }
+                writeln!(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::write_history;
+    use crate::{
+        criterion::tests::write_case,
+        runs::{list_runs, write_summary},
+        scope::Scope,
+    };
+
+    #[test]
+    fn history_shows_change_between_runs() {
+        let root = temp_dir("history");
+        let host = root.join("results/apple-m1");
+        for (run, mean_ns) in [
+            ("2026-03-01T00-00-00Z", 100.0),
+            ("2026-03-02T00-00-00Z", 80.0),
+        ] {
+            let criterion = root.join(run);
+            write_case(
+                &criterion,
+                "non_cryptographic_hash",
+                "XXH3-64",
+                "1024",
+                1024,
+                mean_ns,
+            );
+            write_case(
+                &criterion,
+                "non_cryptographic_hash",
+                "FxHash",
+                "1024",
+                1024,
+                10.0,
+            );
+            let run_dir = host.join("runs").join(run);
+            fs::create_dir_all(&run_dir).expect("create run");
+            write_summary(&run_dir, &criterion).expect("write summary");
+        }
+        fs::create_dir_all(host.join("runs/2026-02-01T00-00-00Z")).expect("create legacy run");
+
+        let runs = list_runs(&host).expect("list runs");
+        let mut out = Vec::new();
+        write_history(&mut out, "apple-m1", &runs, &[Scope::Hash], "XXH3-64")
+            .expect("write history");
+        let out = String::from_utf8(out).expect("utf-8");
+        assert!(out.contains("## non_cryptographic_hash\n\n| Run | 1 KiB |\n| --- | --- |\n"));
+        assert!(out.contains("| 2026-03-01T00-00-00Z | 9.54 GiB/s |\n"));
+        assert!(out.contains("| 2026-03-02T00-00-00Z | 11.92 GiB/s (+25.0%) |\n"));
+        assert!(!out.contains("FxHash"));
+
+        let missing = write_history(&mut Vec::new(), "apple-m1", &runs, &[Scope::Hash], "Nope");
+        assert!(missing.is_err());
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
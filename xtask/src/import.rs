@@ -0,0 +1,612 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    bundle::{MANIFEST_FILE, RUN_DIR, verify_bundle},
+    cli::{HostIdValue, ImportOpts},
+    collect::{add_run, check_host_identity, copy_charts, write_results_markdown},
+    criterion::read_group,
+    environment::{BenchmarkEnvironment, ENV_METADATA_FILE, read_metadata_file},
+    runs::{self, RUNS_DIR, SUMMARY_FILE, read_summary, readme_run_id, run_timestamp},
+    samples::keep_samples,
+    scope::Scope,
+    util::{Result, run_capture, run_capture_checked, slugify, workspace_root},
+};
+
+/// Where archives are unpacked before they are validated.
+const EXTRACT_DIR: &str = "target/xtask/import";
+
+/// What an import source turned out to hold.
+#[derive(Debug)]
+enum Source {
+    /// A `target/criterion` directory, with the `environment.ini` found next to it, if any.
+    Criterion {
+        dir: PathBuf,
+        environment: Option<PathBuf>,
+    },
+    /// A run directory written by `collect`, e.g. a copy of `results/{host}/runs/{id}`.
+    Run(PathBuf),
+    /// A bundle written by `bundle create`.
+    Bundle(PathBuf),
+}
+
+pub fn import_results(opts: &ImportOpts) -> Result<()> {
+    let workspace_root = workspace_root();
+    let result_dir = import_into(
+        opts,
+        &workspace_root.join("results"),
+        &workspace_root.join(EXTRACT_DIR),
+    )?;
+    println!("{}", result_dir.display());
+    Ok(())
+}
+
+/// Import `opts.path` into `results_root`, unpacking archives into `extract_dir`.
+fn import_into(opts: &ImportOpts, results_root: &Path, extract_dir: &Path) -> Result<PathBuf> {
+    if let Some(label) = &opts.label
+        && slugify(label).is_empty()
+    {
+        return Err(
+            format!("invalid --label `{label}`: needs at least one letter or digit").into(),
+        );
+    }
+    if !opts.path.exists() {
+        return Err(format!("{} does not exist", opts.path.display()).into());
+    }
+
+    let root = if opts.path.is_file() {
+        extract_archive(&opts.path, extract_dir)?;
+        extract_dir.to_path_buf()
+    } else {
+        opts.path.clone()
+    };
+    let result = import_from(opts, &root, results_root);
+    if root == extract_dir {
+        fs::remove_dir_all(extract_dir)?;
+    }
+    result
+}
+
+fn import_from(opts: &ImportOpts, root: &Path, results_root: &Path) -> Result<PathBuf> {
+    match locate(root) {
+        Some(Source::Criterion { dir, environment }) => {
+            let environment = opts.environment.clone().or(environment).ok_or_else(|| {
+                format!(
+                    "no {ENV_METADATA_FILE} found in {}; pass --environment with the one of \
+                     the machine that ran the benchmarks",
+                    opts.path.display()
+                )
+            })?;
+            import_criterion(opts, results_root, &dir, &environment)
+        }
+        Some(Source::Run(_) | Source::Bundle(_)) if opts.environment.is_some() => Err(format!(
+            "{} is a collected run with its own {ENV_METADATA_FILE}; --environment only \
+             applies to criterion directories",
+            opts.path.display()
+        )
+        .into()),
+        Some(Source::Run(dir)) => import_run(opts, results_root, &dir, None),
+        Some(Source::Bundle(dir)) => {
+            let manifest = verify_bundle(&dir)?;
+            import_run(opts, results_root, &dir.join(RUN_DIR), Some(manifest.run))
+        }
+        None => Err(format!(
+            "{} holds neither criterion results nor a collected run",
+            opts.path.display()
+        )
+        .into()),
+    }
+}
+
+/// Unpack a tar archive, compressed or not, with the system `tar`.
+///
+/// Archives come from other machines: members outside `extract_dir` are refused before
+/// unpacking, and anything but regular files and directories after.
+pub fn extract_archive(archive: &Path, extract_dir: &Path) -> Result<()> {
+    let members = run_capture_checked("tar", &["-tf", &archive.to_string_lossy()])
+        .map_err(|error| format!("cannot list {}: {error}", archive.display()))?;
+    if let Some(member) = members.lines().find(|member| {
+        Path::new(member).is_absolute()
+            || Path::new(member)
+                .components()
+                .any(|component| component == Component::ParentDir)
+    }) {
+        return Err(format!(
+            "{} has member `{member}` outside the archive root",
+            archive.display()
+        )
+        .into());
+    }
+
+    // The type is the first character of the mode `tar -tv` lists; only `-` and `d` are plain.
+    let listing = run_capture_checked("tar", &["-tvf", &archive.to_string_lossy()])
+        .map_err(|error| format!("cannot list {}: {error}", archive.display()))?;
+    if let Some(line) = listing
+        .lines()
+        .find(|line| !line.starts_with('-') && !line.starts_with('d'))
+    {
+        return Err(format!(
+            "{} has a member that is not a regular file or directory: {line}",
+            archive.display()
+        )
+        .into());
+    }
+
+    if extract_dir.exists() {
+        fs::remove_dir_all(extract_dir)?;
+    }
+    fs::create_dir_all(extract_dir)?;
+    run_capture_checked("tar", &[
+        "-xf",
+        &archive.to_string_lossy(),
+        "-C",
+        &extract_dir.to_string_lossy(),
+    ])
+    .map_err(|error| format!("cannot unpack {}: {error}", archive.display()))?;
+    if let Err(error) = check_regular_tree(extract_dir) {
+        fs::remove_dir_all(extract_dir)?;
+        return Err(format!("cannot unpack {}: {error}", archive.display()).into());
+    }
+    Ok(())
+}
+
+/// Refuse symlinks and special files, which could pull in files from outside `dir`.
+fn check_regular_tree(dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_type = fs::symlink_metadata(&path)?.file_type();
+        if file_type.is_dir() {
+            check_regular_tree(&path)?;
+        } else if !file_type.is_file() {
+            return Err(format!("{} is not a regular file or directory", path.display()).into());
+        }
+    }
+    Ok(())
+}
+
+/// Find the results in `root`, looking through the single top-level directory archives
+/// usually wrap their content in.
+fn locate(root: &Path) -> Option<Source> {
+    let mut dir = root.to_path_buf();
+    for _ in 0..4 {
+        if dir.join(MANIFEST_FILE).is_file() {
+            return Some(Source::Bundle(dir));
+        }
+        if dir.join(ENV_METADATA_FILE).is_file() && dir.join(SUMMARY_FILE).is_file() {
+            return Some(Source::Run(dir));
+        }
+        for candidate in [
+            dir.clone(),
+            dir.join("criterion"),
+            dir.join("target/criterion"),
+        ] {
+            if is_criterion_dir(&candidate) {
+                let environment = [&candidate, &dir]
+                    .into_iter()
+                    .map(|dir| dir.join(ENV_METADATA_FILE))
+                    .find(|path| path.is_file());
+                return Some(Source::Criterion {
+                    dir: candidate,
+                    environment,
+                });
+            }
+        }
+        let mut children = fs::read_dir(&dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir());
+        let only = children.next()?;
+        if children.next().is_some() {
+            return None;
+        }
+        dir = only;
+    }
+    None
+}
+
+fn is_criterion_dir(dir: &Path) -> bool {
+    Scope::all()
+        .iter()
+        .flat_map(|scope| scope.summary_groups())
+        .any(|group| dir.join(group).is_dir())
+}
+
+fn import_criterion(
+    opts: &ImportOpts,
+    results_root: &Path,
+    criterion_dir: &Path,
+    environment_file: &Path,
+) -> Result<PathBuf> {
+    let environment = read_metadata_file(environment_file)?;
+    let environment = with_import_label(environment, opts);
+    validate_criterion_dir(criterion_dir)?;
+    let host_dir = import_host_dir(opts, results_root, &environment)?;
+
+    add_run(&host_dir, &runs::new_run_id(), |run_dir| {
+        copy_charts(criterion_dir, run_dir)?;
+        write_import_readme(run_dir, criterion_dir, opts, &environment)?;
+        environment.write_metadata_file(run_dir)?;
+        keep_samples(criterion_dir, run_dir);
+        runs::write_summary(run_dir, criterion_dir)
+    })
+}
+
+/// Import a collected run under `run_id`, or else the id its README or directory name records.
+fn import_run(
+    opts: &ImportOpts,
+    results_root: &Path,
+    run_dir: &Path,
+    run_id: Option<String>,
+) -> Result<PathBuf> {
+    let environment = read_metadata_file(&run_dir.join(ENV_METADATA_FILE))?;
+    let relabel = opts.label.is_some();
+    let environment = with_import_label(environment, opts);
+    read_summary(run_dir)?;
+    let missing: Vec<&str> = Scope::all()
+        .iter()
+        .flat_map(|scope| scope.charts())
+        .map(|chart| chart.dest_path)
+        .filter(|chart| !run_dir.join("charts").join(chart).is_file())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} is missing charts: {}",
+            run_dir.display(),
+            missing.join(", ")
+        )
+        .into());
+    }
+    let host_dir = import_host_dir(opts, results_root, &environment)?;
+
+    // Keep the id the run was collected under, so importing it twice is noticed.
+    let run_id = run_id
+        .or_else(|| {
+            let readme = fs::read_to_string(run_dir.join("README.md")).ok()?;
+            readme_run_id(&readme)
+        })
+        .or_else(|| {
+            let name = run_dir.file_name()?.to_string_lossy().into_owned();
+            run_timestamp(&name).map(|_| name)
+        })
+        .unwrap_or_else(runs::new_run_id);
+    let existing = host_dir.join(RUNS_DIR).join(&run_id);
+    if existing.exists() && !opts.force {
+        return Err(format!(
+            "{} already exists; pass --force to replace it",
+            existing.display()
+        )
+        .into());
+    }
+
+    add_run(&host_dir, &run_id, |staging_dir| {
+        copy_dir_all(run_dir, staging_dir)?;
+        if relabel {
+            environment.write_metadata_file(staging_dir)?;
+        }
+        Ok(())
+    })
+}
+
+/// `--label` replaces the label recorded by the other machine, if any.
+fn with_import_label(environment: BenchmarkEnvironment, opts: &ImportOpts) -> BenchmarkEnvironment {
+    let label = opts
+        .label
+        .clone()
+        .or_else(|| environment.label().map(str::to_owned));
+    environment.with_label(label)
+}
+
+/// Host directory of the imported results; refuses to mix them with another machine's.
+fn import_host_dir(
+    opts: &ImportOpts,
+    results_root: &Path,
+    environment: &BenchmarkEnvironment,
+) -> Result<PathBuf> {
+    // Without a recorded platform the host id would name the importing machine's OS.
+    if opts.host_id == HostIdValue::CpuOs && environment.platform().is_none() {
+        return Err(
+            "the environment file has no `platform` key for --host-id cpu-os; add one \
+             (e.g. `platform = linux`) or import with --host-id cpu"
+                .into(),
+        );
+    }
+    let host_dir = results_root.join(environment.result_name(opts.host_id));
+    check_host_identity(&host_dir, environment, opts.force)?;
+    Ok(host_dir)
+}
+
+/// Every published chart must exist and every summary group must hold results.
+fn validate_criterion_dir(criterion_dir: &Path) -> Result<()> {
+    let missing: Vec<String> = Scope::all()
+        .iter()
+        .flat_map(|scope| scope.charts())
+        .map(|chart| chart.source(criterion_dir))
+        .filter(|source| !source.is_file())
+        .map(|source| {
+            source
+                .strip_prefix(criterion_dir)
+                .unwrap_or(&source)
+                .display()
+                .to_string()
+        })
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} is missing criterion charts (all scopes must be benchmarked): {}",
+            criterion_dir.display(),
+            missing.join(", ")
+        )
+        .into());
+    }
+    for &scope in Scope::all() {
+        for &group in scope.summary_groups() {
+            if read_group(criterion_dir, group)?.is_empty() {
+                return Err(
+                    format!("{} has no results for `{group}`", criterion_dir.display()).into(),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_import_readme(
+    result_dir: &Path,
+    criterion_dir: &Path,
+    opts: &ImportOpts,
+    environment: &BenchmarkEnvironment,
+) -> Result<()> {
+    let mut file = fs::File::create(result_dir.join("README.md"))?;
+    let imported_at =
+        run_capture("date", &["+%Y-%m-%d %H:%M:%S %z"]).unwrap_or_else(|| "unknown".to_owned());
+    let source = opts.path.file_name().map_or_else(
+        || opts.path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+
+    writeln!(file, "# Benchmark Results")?;
+    writeln!(file)?;
+    writeln!(file, "Imported at {imported_at} from `{source}`.")?;
+    writeln!(file)?;
+    environment.write_markdown(&mut file)?;
+    write_results_markdown(&mut file, criterion_dir, &opts.references, environment)
+}
+
+fn copy_dir_all(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = fs::symlink_metadata(entry.path())?.file_type();
+        if file_type.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), target)?;
+        } else {
+            return Err(format!(
+                "{} is not a regular file or directory",
+                entry.path().display()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{Source, import_into, locate};
+    use crate::{
+        bundle::write_bundle_dir,
+        cli::{HostIdValue, ImportOpts},
+        criterion::tests::write_case,
+        environment::read_metadata,
+        runs::{latest_run_dir, list_runs, read_summary},
+        scope::Scope,
+    };
+
+    const ENVIRONMENT: &str = "[environment]\ncpu = Apple M1\nos = macOS 15.3\nplatform = macos\n\n[placement]\ncpus = 2-3\nisolated = false\nnice = inherited\n";
+
+    fn write_criterion(dir: &Path) {
+        for &scope in Scope::all() {
+            for &group in scope.summary_groups() {
+                write_case(dir, group, "Case", "1024", 1024, 50.0);
+            }
+            for chart in scope.charts() {
+                let source = chart.source(dir);
+                fs::create_dir_all(source.parent().expect("chart dir")).expect("create chart dir");
+                fs::write(source, "<svg/>").expect("write chart");
+            }
+        }
+    }
+
+    fn opts(path: PathBuf) -> ImportOpts {
+        ImportOpts {
+            path,
+            environment: None,
+            host_id: HostIdValue::CpuOs,
+            label: None,
+            force: false,
+            references: vec![],
+        }
+    }
+
+    #[test]
+    fn imports_criterion_dir_and_run_bundle() {
+        let root = temp_dir("import");
+        let upload = root.join("upload");
+        write_criterion(&upload.join("target/criterion"));
+        fs::write(upload.join("environment.ini"), ENVIRONMENT).expect("write environment");
+        assert!(matches!(
+            locate(&upload),
+            Some(Source::Criterion {
+                environment: Some(_),
+                ..
+            })
+        ));
+
+        let results = root.join("results");
+        let run = import_into(&opts(upload.clone()), &results, &root.join("extract"))
+            .expect("import criterion dir");
+        let host_dir = results.join("apple-m1-macos");
+        assert_eq!(
+            run.parent().and_then(Path::parent),
+            Some(host_dir.as_path())
+        );
+        assert!(read_summary(&run).expect("read summary").is_some());
+        let readme = fs::read_to_string(run.join("README.md")).expect("read README");
+        assert!(readme.contains("from `upload`."));
+        assert!(readme.contains("## Results"));
+        let environment = read_metadata(&latest_run_dir(&host_dir)).expect("recorded environment");
+        assert_eq!(environment.platform(), Some("macos"));
+        let metadata = fs::read_to_string(run.join("environment.ini")).expect("read metadata");
+        assert!(metadata.contains("[placement]\ncpus = 2-3\n"));
+
+        // The collected run moves on to another repository checkout as is.
+        let other = root.join("other-results");
+        let copy =
+            import_into(&opts(run.clone()), &other, &root.join("extract")).expect("import run");
+        assert_eq!(copy.file_name(), run.file_name());
+        let error = import_into(&opts(run.clone()), &other, &root.join("extract"))
+            .expect_err("duplicate run");
+        assert!(error.to_string().contains("--force"));
+        let mut with_environment = opts(run.clone());
+        with_environment.environment = Some(upload.join("environment.ini"));
+        let error = import_into(&with_environment, &other, &root.join("extract"))
+            .expect_err("--environment for a run");
+        assert!(error.to_string().contains("only applies to criterion"));
+
+        // So does a bundle of it, under the run id of its manifest.
+        let bundle = root.join("bundle");
+        let run_id = run.file_name().expect("run id").to_string_lossy();
+        write_bundle_dir(
+            &bundle,
+            "apple-m1-macos",
+            &run_id,
+            &run,
+            &upload.join("target/criterion"),
+        )
+        .expect("write bundle");
+        let third = root.join("third-results");
+        let copy =
+            import_into(&opts(bundle), &third, &root.join("extract")).expect("import bundle");
+        assert_eq!(copy, third.join("apple-m1-macos/runs").join(&*run_id));
+
+        let mut labeled = opts(run);
+        labeled.label = Some("lab".to_owned());
+        let copy = import_into(&labeled, &other, &root.join("extract")).expect("import labeled");
+        assert!(copy.starts_with(other.join("apple-m1-macos-lab")));
+        assert_eq!(
+            list_runs(&other.join("apple-m1-macos"))
+                .expect("list")
+                .len(),
+            1
+        );
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn imports_archive_and_validates() {
+        let root = temp_dir("import-archive");
+        let upload = root.join("m1-results/criterion");
+        write_criterion(&upload);
+        let archive = root.join("m1-results.tar.gz");
+        let status = std::process::Command::new("tar")
+            .args([
+                "-czf",
+                &archive.to_string_lossy(),
+                "-C",
+                &root.to_string_lossy(),
+                "m1-results",
+            ])
+            .status()
+            .expect("run tar");
+        assert!(status.success());
+
+        let results = root.join("results");
+        let extract = root.join("extract");
+        let error =
+            import_into(&opts(archive.clone()), &results, &extract).expect_err("no environment");
+        assert!(error.to_string().contains("--environment"));
+        assert!(!extract.exists());
+
+        let environment = root.join("m1.ini");
+        fs::write(&environment, "[environment]\ncpu = Apple M1\n").expect("write environment");
+        let mut with_environment = opts(archive.clone());
+        with_environment.environment = Some(environment.clone());
+        let error = import_into(&with_environment, &results, &extract).expect_err("no platform");
+        assert!(error.to_string().contains("--host-id cpu"));
+
+        with_environment.host_id = HostIdValue::Cpu;
+        let run = import_into(&with_environment, &results, &extract).expect("import archive");
+        assert!(run.starts_with(results.join("apple-m1")));
+        assert!(run.join("charts").is_dir());
+
+        fs::remove_file(Scope::Prng.charts()[0].source(&upload)).expect("remove chart");
+        with_environment.path = upload;
+        let error = import_into(&with_environment, &results, &extract).expect_err("missing chart");
+        assert!(error.to_string().contains("missing criterion charts"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn rejects_links_and_escaping_members() {
+        let root = temp_dir("import-links");
+        let upload = root.join("upload/criterion");
+        write_criterion(&upload);
+        let tar = |args: &[&str]| {
+            let status = std::process::Command::new("tar")
+                .args(args)
+                .current_dir(&root)
+                .status()
+                .expect("run tar");
+            assert!(status.success());
+        };
+        let results = root.join("results");
+        let extract = root.join("extract");
+
+        let estimates = upload
+            .join(Scope::Hash.summary_groups()[0])
+            .join("Case/1024/new/estimates.json");
+        fs::remove_file(&estimates).expect("remove estimates");
+        std::os::unix::fs::symlink("/etc/passwd", &estimates).expect("symlink");
+        tar(&["-cf", "links.tar", "upload"]);
+        let error = import_into(&opts(root.join("links.tar")), &results, &extract)
+            .expect_err("symlink member");
+        assert!(error.to_string().contains("not a regular file"), "{error}");
+        assert!(!extract.exists());
+
+        fs::write(root.join("escape"), "").expect("write escape");
+        fs::create_dir_all(root.join("nested")).expect("create nested");
+        tar(&["-cPf", "escape.tar", "nested/../escape"]);
+        let error = import_into(&opts(root.join("escape.tar")), &results, &extract)
+            .expect_err("escaping member");
+        assert!(
+            error.to_string().contains("outside the archive root"),
+            "{error}"
+        );
+        assert!(!extract.exists());
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
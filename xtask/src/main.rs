@@ -1,10 +1,32 @@
+mod alignment;
 mod bench;
+mod build_config;
+mod bundle;
+mod cache;
+mod charts;
 mod cli;
 mod collect;
+mod criterion;
+mod energy;
 mod environment;
+mod export;
+mod hardware;
+mod history;
+mod import;
+mod metadata;
+mod normalize;
+mod perf;
+mod placement;
+mod preflight;
+mod reanalyze;
+mod report;
 mod results;
+mod runs;
+mod samples;
 mod scope;
+mod toolchains;
 mod util;
+mod versions;
 
 use clap::Parser;
 
@@ -15,7 +37,14 @@ fn run() -> Result<()> {
     match cmd {
         Command::Run(args) => bench::run_benchmarks(&args),
         Command::Collect(args) => collect::collect_results(args),
-        Command::Aggregate => results::aggregate_results(),
+        Command::Aggregate(args) => results::aggregate_results(&args),
+        Command::History(args) => history::print_history(&args),
+        Command::Report(args) => report::write_report(&args),
+        Command::Export(args) => export::export_results(&args),
+        Command::Import(args) => import::import_results(&args),
+        Command::Bundle(args) => bundle::run_bundle(&args),
+        Command::MigrateResults(args) => metadata::migrate_results(&args),
+        Command::Reanalyze(args) => reanalyze::reanalyze_results(&args),
     }
 }
 
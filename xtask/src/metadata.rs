@@ -0,0 +1,424 @@
+use std::{fs, path::Path};
+
+use crate::{
+    cli::MigrateResultsOpts,
+    environment::{BenchmarkEnvironment, ENV_METADATA_FILE, ENV_METADATA_FILE_LEGACY},
+    runs::list_runs,
+    util::{self, workspace_root},
+};
+
+/// Version of the `environment.ini` layout this `xtask` writes.
+///
+/// 1. Sectionless `key = value` lines, first in `environment.meta`.
+/// 2. `[environment]`, `[hardware]`, `[build]`, `[dependencies.{scope}]`, `[placement]` and
+///    `[preflight]` sections. Files written before `schema_version` was recorded lack the key.
+pub const SCHEMA_VERSION: u32 = 2;
+pub const SCHEMA_KEY: &str = "schema_version";
+
+/// Upgrade from version `index + 1` to `index + 2`.
+const MIGRATIONS: &[fn(&mut Ini)] = &[migrate_v1_to_v2];
+
+/// `environment.ini` as sections of `key = value` entries, values still escaped.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Ini {
+    /// In file order; entries before the first header are in a section named `""`.
+    pub sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// An [`Ini`] upgraded to [`SCHEMA_VERSION`].
+#[derive(Debug)]
+pub struct Migrated {
+    pub ini: Ini,
+    /// Schema version of the input.
+    pub from: u32,
+}
+
+impl Ini {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut ini = Self::default();
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .filter(|name| !name.trim().is_empty())
+                    .ok_or_else(|| {
+                        format!("line {}: invalid section header `{line}`", index + 1)
+                    })?;
+                ini.sections.push((name.trim().to_owned(), Vec::new()));
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                format!("line {}: expected `key = value`, got `{line}`", index + 1)
+            })?;
+            if ini.sections.is_empty() {
+                ini.sections.push((String::new(), Vec::new()));
+            }
+            let entries = &mut ini.sections.last_mut().expect("non-empty").1;
+            entries.push((key.trim().to_owned(), value.trim().to_owned()));
+        }
+        Ok(ini)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, entries) in &self.sections {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            if !name.is_empty() {
+                out.push_str(&format!("[{name}]\n"));
+            }
+            for (key, value) in entries {
+                out.push_str(&format!("{key} = {value}\n"));
+            }
+        }
+        out
+    }
+
+    /// Entries of the first `[section]`; empty when there is none.
+    pub fn entries(&self, section: &str) -> &[(String, String)] {
+        self.sections
+            .iter()
+            .find(|(name, _)| name == section)
+            .map_or(&[], |(_, entries)| entries)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .iter()
+            .filter(|(name, _)| name == section)
+            .flat_map(|(_, entries)| entries)
+            .find(|(entry, _)| entry == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Schema version of the file: its `schema_version`, or else inferred from its layout.
+    pub fn version(&self) -> Result<u32, String> {
+        if let Some(version) = self.get("environment", SCHEMA_KEY) {
+            return version
+                .parse()
+                .map_err(|_| format!("invalid {SCHEMA_KEY} `{version}`"));
+        }
+        let sectioned = self.sections.iter().any(|(name, _)| !name.is_empty());
+        Ok(if sectioned { 2 } else { 1 })
+    }
+
+    pub fn migrate(mut self) -> Result<Migrated, String> {
+        let from = self.version()?;
+        if from == 0 || from > SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {from} is not supported (this xtask reads 1 to {SCHEMA_VERSION}); \
+                 update xtask"
+            ));
+        }
+        for migration in &MIGRATIONS[from as usize - 1..] {
+            migration(&mut self);
+        }
+        if self.get("environment", SCHEMA_KEY).is_none() {
+            let schema = (SCHEMA_KEY.to_owned(), SCHEMA_VERSION.to_string());
+            match self
+                .sections
+                .iter_mut()
+                .find(|(name, _)| name == "environment")
+            {
+                Some((_, entries)) => entries.insert(0, schema),
+                None => self
+                    .sections
+                    .insert(0, ("environment".to_owned(), vec![schema])),
+            }
+        }
+        Ok(Migrated { ini: self, from })
+    }
+
+    /// Check the layout of the file; the section decoders of [`BenchmarkEnvironment`]
+    /// reject unknown sections, keys and values.
+    pub fn validate(&self) -> Result<(), String> {
+        let version = self.version()?;
+        if version != SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {version} instead of {SCHEMA_VERSION}; run `cargo xtask migrate-results`"
+            ));
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for (name, entries) in &self.sections {
+            if name.is_empty() {
+                return Err("entries before the first section".to_owned());
+            }
+            if seen.contains(&name.as_str()) {
+                return Err(format!("duplicate section [{name}]"));
+            }
+            seen.push(name);
+            let mut keys: Vec<&str> = Vec::new();
+            for (key, _) in entries {
+                if key.is_empty() {
+                    return Err(format!("empty key in [{name}]"));
+                }
+                if keys.contains(&key.as_str()) {
+                    return Err(format!("duplicate key `{key}` in [{name}]"));
+                }
+                keys.push(key);
+            }
+        }
+        match self.get("environment", "cpu") {
+            Some(cpu) if !cpu.is_empty() => Ok(()),
+            _ => Err("missing metadata key: cpu".to_owned()),
+        }
+    }
+}
+
+/// Sectionless lines become the `[environment]` section.
+fn migrate_v1_to_v2(ini: &mut Ini) {
+    for (name, _) in &mut ini.sections {
+        if name.is_empty() {
+            *name = "environment".to_owned();
+        }
+    }
+}
+
+pub fn migrate_results(opts: &MigrateResultsOpts) -> util::Result<()> {
+    let summary = migrate_results_dir(&workspace_root().join("results"), opts.check)?;
+    let verb = if opts.check { "to migrate" } else { "migrated" };
+    println!(
+        "{} {verb}, {} up to date",
+        summary.migrated, summary.current
+    );
+    if summary.failed > 0 {
+        return Err(format!("{} metadata file(s) failed validation", summary.failed).into());
+    }
+    if opts.check && summary.migrated > 0 {
+        return Err(format!(
+            "{} metadata file(s) need `cargo xtask migrate-results`",
+            summary.migrated
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct MigrationSummary {
+    /// Files migrated, or that need migrating with `check`.
+    migrated: usize,
+    current: usize,
+    failed: usize,
+}
+
+/// Upgrade the metadata of every run (and legacy flat host directory) in `results_root`.
+fn migrate_results_dir(results_root: &Path, check: bool) -> util::Result<MigrationSummary> {
+    let mut summary = MigrationSummary::default();
+    if !results_root.is_dir() {
+        return Ok(summary);
+    }
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(results_root)? {
+        let host_dir = entry?.path();
+        if host_dir.is_dir() {
+            dirs.push(host_dir.clone());
+            dirs.extend(list_runs(&host_dir)?.into_iter().map(|(_, dir)| dir));
+        }
+    }
+    dirs.sort();
+
+    for dir in dirs {
+        let (path, legacy_name) = if dir.join(ENV_METADATA_FILE).is_file() {
+            (dir.join(ENV_METADATA_FILE), false)
+        } else if dir.join(ENV_METADATA_FILE_LEGACY).is_file() {
+            (dir.join(ENV_METADATA_FILE_LEGACY), true)
+        } else {
+            continue;
+        };
+        let parsed = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|raw| Ini::parse(&raw));
+        let migrated = parsed.clone().and_then(Ini::migrate).and_then(|migrated| {
+            BenchmarkEnvironment::from_ini(&migrated.ini)?;
+            Ok(migrated)
+        });
+        let (parsed, migrated) = match (parsed, migrated) {
+            (Ok(parsed), Ok(migrated)) => (parsed, migrated),
+            (Err(error), _) | (_, Err(error)) => {
+                eprintln!("error: {}: {error}", path.display());
+                summary.failed += 1;
+                continue;
+            }
+        };
+        if migrated.ini == parsed && !legacy_name {
+            summary.current += 1;
+            continue;
+        }
+        summary.migrated += 1;
+        let verb = if check { "needs migration" } else { "migrated" };
+        let change = if migrated.from < SCHEMA_VERSION {
+            format!("from schema {} to {SCHEMA_VERSION}", migrated.from)
+        } else if legacy_name {
+            format!("to {ENV_METADATA_FILE}")
+        } else {
+            format!("to record {SCHEMA_KEY}")
+        };
+        println!("{}: {verb} {change}", path.display());
+        if !check {
+            fs::write(dir.join(ENV_METADATA_FILE), migrated.ini.render())?;
+            if legacy_name {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{Ini, MigrationSummary, SCHEMA_VERSION, migrate_results_dir};
+    use crate::environment::BenchmarkEnvironment;
+
+    #[test]
+    fn migrates_every_schema_version() {
+        let v1 = Ini::parse("cpu = Apple M1\nos = macOS\n").expect("parse v1");
+        assert_eq!(v1.version(), Ok(1));
+        let migrated = v1.migrate().expect("migrate v1");
+        assert_eq!(migrated.from, 1);
+        assert_eq!(
+            migrated.ini.render(),
+            "[environment]\nschema_version = 2\ncpu = Apple M1\nos = macOS\n"
+        );
+        assert_eq!(migrated.ini.validate(), Ok(()));
+
+        // Written before `schema_version` was recorded.
+        let unversioned =
+            Ini::parse("[environment]\ncpu = Apple M4\n\n[dependencies.hash]\nblake3 = 1.8.2\n")
+                .expect("parse unversioned");
+        let migrated = unversioned.migrate().expect("migrate unversioned");
+        assert_eq!(migrated.from, 2);
+        assert_eq!(migrated.ini.get("environment", "schema_version"), Some("2"));
+        assert_eq!(
+            migrated.ini.get("dependencies.hash", "blake3"),
+            Some("1.8.2")
+        );
+        assert_eq!(migrated.ini.validate(), Ok(()));
+
+        let current = migrated.ini.clone().migrate().expect("migrate current");
+        assert_eq!(current.from, SCHEMA_VERSION);
+        assert_eq!(current.ini, migrated.ini);
+
+        let future = Ini::parse("[environment]\nschema_version = 99\ncpu = X\n").expect("parse");
+        assert!(
+            future
+                .migrate()
+                .is_err_and(|error| error.contains("update xtask"))
+        );
+    }
+
+    #[test]
+    fn validation_rejects_unknown_keys_and_sections() {
+        let check = |body: &str| {
+            Ini::parse(&format!(
+                "[environment]\nschema_version = 2\ncpu = X\n{body}"
+            ))
+            .map(|ini| BenchmarkEnvironment::from_ini(&ini).map(drop))
+            .expect("parse")
+        };
+        assert_eq!(check("\n[dependencies.hash]\nblake3 = 1.8.3\n"), Ok(()));
+        assert_eq!(
+            check("\n[preflight]\nturbo = enabled\nturbo_warning = boost\n"),
+            Ok(())
+        );
+        assert_eq!(
+            check("cpus = 4\n"),
+            Err("unknown key `cpus` in [environment]".to_owned())
+        );
+        assert_eq!(
+            check("\n[hardware]\ncores = eight\n"),
+            Err("[hardware] cores: `eight` is not a number".to_owned())
+        );
+        assert_eq!(
+            check("\n[dependencies.gpu]\nwgpu = 1\n"),
+            Err("unknown section [dependencies.gpu]".to_owned())
+        );
+        assert_eq!(
+            check("cpu = Y\n"),
+            Err("duplicate key `cpu` in [environment]".to_owned())
+        );
+        assert_eq!(
+            check("\n[build]\nrustflags = \nlinker = lld\n"),
+            Err("unknown key `linker` in [build]".to_owned())
+        );
+        assert_eq!(
+            check("\n[preflight]\nturbo = enabled\nfan_warning = loud\n"),
+            Err("unknown key `fan_warning` in [preflight]".to_owned())
+        );
+        assert_eq!(
+            check("\n[placement]\ncpus = two\n"),
+            Err(
+                "[placement] cpus: invalid CPU list `two`: invalid digit found in string"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            check("\n[gpu]\nname = X\n"),
+            Err("unknown section [gpu]".to_owned())
+        );
+        assert!(Ini::parse("[environment]\ncpu\n").is_err());
+    }
+
+    #[test]
+    fn migrate_results_rewrites_old_files() {
+        let root = temp_dir("metadata-migrate");
+        let legacy_host = root.join("apple-m1");
+        let run = root.join("apple-m4-macos/runs/2026-03-01T00-00-00Z");
+        let broken = root.join("broken/runs/2026-03-01T00-00-00Z");
+        for dir in [&legacy_host, &run, &broken] {
+            fs::create_dir_all(dir).expect("create dir");
+        }
+        fs::write(legacy_host.join("environment.meta"), "cpu = Apple M1\n").expect("write meta");
+        fs::write(
+            run.join("environment.ini"),
+            "[environment]\nschema_version = 2\ncpu = Apple M4\n",
+        )
+        .expect("write current");
+        fs::write(
+            broken.join("environment.ini"),
+            "[environment]\ncpu = X\nfoo = 1\n",
+        )
+        .expect("write broken");
+
+        let summary = migrate_results_dir(&root, true).expect("check");
+        assert_eq!(summary, MigrationSummary {
+            migrated: 1,
+            current: 1,
+            failed: 1
+        });
+        assert!(legacy_host.join("environment.meta").is_file());
+
+        migrate_results_dir(&root, false).expect("migrate");
+        assert!(!legacy_host.join("environment.meta").exists());
+        let migrated =
+            fs::read_to_string(legacy_host.join("environment.ini")).expect("read migrated");
+        assert!(migrated.starts_with("[environment]\nschema_version = 2\ncpu = Apple M1\n"));
+        let summary = migrate_results_dir(&root, true).expect("check again");
+        assert_eq!(summary, MigrationSummary {
+            migrated: 0,
+            current: 2,
+            failed: 1
+        });
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
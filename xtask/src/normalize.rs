@@ -0,0 +1,332 @@
+use std::{fmt::Write as _, io::Write, path::Path};
+
+use crate::{
+    criterion::{BenchmarkRecord, read_group},
+    scope::Scope,
+    util::Result,
+};
+
+/// Algorithm the others are compared to. A trailing `*` matches a family, e.g.
+/// `XOR-128-*` for whichever SIMD variant the host runs, taking the fastest per size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference(String);
+
+impl Reference {
+    pub fn new(pattern: &str) -> Self {
+        Self(pattern.to_owned())
+    }
+
+    pub fn matches(&self, function: &str) -> bool {
+        match self.0.strip_suffix('*') {
+            Some(prefix) => function.starts_with(prefix),
+            None => function == self.0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// References to try for a scope: those given on the command line, else the defaults.
+/// Each group is normalized against the first reference it contains.
+pub fn references(scope: Scope, requested: &[String]) -> Vec<Reference> {
+    if requested.is_empty() {
+        scope
+            .default_references()
+            .iter()
+            .map(|pattern| Reference::new(pattern))
+            .collect()
+    } else {
+        requested
+            .iter()
+            .map(|pattern| Reference::new(pattern))
+            .collect()
+    }
+}
+
+/// Throughput of each algorithm relative to the reference, per input size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeTable {
+    pub reference: String,
+    /// Display parameters (input sizes or counts), in increasing order.
+    pub parameters: Vec<String>,
+    /// Algorithm and its ratio at each of `parameters`.
+    pub rows: Vec<(String, Vec<Option<f64>>)>,
+}
+
+impl RelativeTable {
+    /// `None` when no reference is present in `records`.
+    pub fn build(records: &[BenchmarkRecord], references: &[Reference]) -> Option<Self> {
+        let reference = references.iter().find(|reference| {
+            records
+                .iter()
+                .any(|record| reference.matches(&record.function))
+        })?;
+
+        let mut amounts: Vec<(u64, String)> = Vec::new();
+        for record in records {
+            let Some(throughput) = record.throughput else {
+                continue;
+            };
+            if !amounts
+                .iter()
+                .any(|(amount, _)| *amount == throughput.amount())
+            {
+                amounts.push((throughput.amount(), record.display_parameter()));
+            }
+        }
+        amounts.sort_unstable();
+
+        let rate_at = |record: &BenchmarkRecord, amount: u64| {
+            (record.throughput.map(|throughput| throughput.amount()) == Some(amount))
+                .then(|| record.throughput_per_sec())
+                .flatten()
+        };
+        let reference_rates: Vec<Option<f64>> = amounts
+            .iter()
+            .map(|&(amount, _)| {
+                records
+                    .iter()
+                    .filter(|record| reference.matches(&record.function))
+                    .filter_map(|record| rate_at(record, amount))
+                    .reduce(f64::max)
+            })
+            .collect();
+
+        let mut rows: Vec<(String, Vec<Option<f64>>)> = Vec::new();
+        for record in records {
+            let index = rows
+                .iter()
+                .position(|(function, _)| *function == record.function)
+                .unwrap_or_else(|| {
+                    rows.push((record.function.clone(), vec![None; amounts.len()]));
+                    rows.len() - 1
+                });
+            for (column, &(amount, _)) in amounts.iter().enumerate() {
+                if let (Some(rate), Some(reference_rate)) =
+                    (rate_at(record, amount), reference_rates[column])
+                {
+                    rows[index].1[column] = Some(rate / reference_rate);
+                }
+            }
+        }
+        // Fastest first, by geometric mean over sizes.
+        rows.sort_by(|a, b| geometric_mean(&b.1).total_cmp(&geometric_mean(&a.1)));
+
+        Some(Self {
+            reference: reference.name().to_owned(),
+            parameters: amounts.into_iter().map(|(_, display)| display).collect(),
+            rows,
+        })
+    }
+
+    fn write_markdown(&self, out: &mut String) -> std::fmt::Result {
+        write!(out, "| Algorithm |")?;
+        for parameter in &self.parameters {
+            write!(out, " {parameter} |")?;
+        }
+        writeln!(out)?;
+        writeln!(out, "| --- |{}", " --- |".repeat(self.parameters.len()))?;
+        for (function, ratios) in &self.rows {
+            write!(out, "| `{function}` |")?;
+            for ratio in ratios {
+                write!(out, " {} |", heat_cell(*ratio))?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Geometric mean of the known ratios; `0.0` when there are none.
+pub fn geometric_mean(ratios: &[Option<f64>]) -> f64 {
+    let known: Vec<f64> = ratios.iter().flatten().copied().collect();
+    if known.is_empty() {
+        return 0.0;
+    }
+    (known.iter().map(|ratio| ratio.ln()).sum::<f64>() / known.len() as f64).exp()
+}
+
+/// Ratio with a colored square so tables read as a heatmap.
+pub fn heat_cell(ratio: Option<f64>) -> String {
+    let Some(ratio) = ratio else {
+        return "-".to_owned();
+    };
+    let square = match ratio {
+        r if r >= 1.1 => "🟩",
+        r if r >= 0.9 => "⬜",
+        r if r >= 0.5 => "🟨",
+        r if r >= 0.25 => "🟧",
+        _ => "🟥",
+    };
+    format!("{square} {ratio:.2}×")
+}
+
+pub const HEAT_LEGEND: &str =
+    "🟩 ≥ 1.10× · ⬜ 0.90–1.10× · 🟨 0.50–0.90× · 🟧 0.25–0.50× · 🟥 < 0.25×";
+
+/// Write one relative-throughput table per summary group of `scope` for this host.
+pub fn write_relative_markdown(
+    writer: &mut impl Write,
+    criterion_dir: &Path,
+    scope: Scope,
+    references: &[Reference],
+) -> Result<()> {
+    let mut content = String::new();
+    for &group in scope.summary_groups() {
+        let Some(table) = RelativeTable::build(&read_group(criterion_dir, group)?, references)
+        else {
+            continue;
+        };
+        writeln!(content)?;
+        writeln!(
+            content,
+            "#### {} Relative to `{}`",
+            scope.group_title(group),
+            table.reference
+        )?;
+        writeln!(content)?;
+        writeln!(
+            content,
+            "Throughput divided by the reference at each size. {HEAT_LEGEND}"
+        )?;
+        writeln!(content)?;
+        table.write_markdown(&mut content)?;
+    }
+    writer.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Relative throughput of one group across hosts: a geometric-mean overview with hosts as
+/// columns, then the per-size table of each host.
+pub fn write_relative_results(
+    content: &mut String,
+    title: &str,
+    hosts: &[(&str, RelativeTable)],
+) -> std::fmt::Result {
+    let Some((_, first)) = hosts.first() else {
+        return Ok(());
+    };
+    let mut functions: Vec<&str> = Vec::new();
+    for (_, table) in hosts {
+        for (function, _) in &table.rows {
+            if !functions.contains(&function.as_str()) {
+                functions.push(function);
+            }
+        }
+    }
+
+    writeln!(content)?;
+    writeln!(content, "### {title}")?;
+    writeln!(content)?;
+    writeln!(
+        content,
+        "Geometric mean over sizes of throughput divided by `{}` on the same host. {HEAT_LEGEND}",
+        first.reference
+    )?;
+    writeln!(content)?;
+    write!(content, "| Algorithm |")?;
+    for (host, table) in hosts {
+        if table.reference == first.reference {
+            write!(content, " {host} |")?;
+        } else {
+            write!(content, " {host} (vs `{}`) |", table.reference)?;
+        }
+    }
+    writeln!(content)?;
+    writeln!(content, "| --- |{}", " --- |".repeat(hosts.len()))?;
+    for function in functions {
+        write!(content, "| `{function}` |")?;
+        for (_, table) in hosts {
+            let ratio = table
+                .rows
+                .iter()
+                .find(|(name, _)| name == function)
+                .map(|(_, ratios)| geometric_mean(ratios))
+                .filter(|&mean| mean > 0.0);
+            write!(content, " {} |", heat_cell(ratio))?;
+        }
+        writeln!(content)?;
+    }
+
+    for (host, table) in hosts {
+        writeln!(content)?;
+        writeln!(content, "<details><summary>{host}, per size</summary>")?;
+        writeln!(content)?;
+        table.write_markdown(content)?;
+        writeln!(content)?;
+        writeln!(content, "</details>")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reference, RelativeTable, geometric_mean, heat_cell, write_relative_results};
+    use crate::criterion::{Throughput, tests::record};
+
+    #[test]
+    fn wildcard_reference_takes_fastest_variant() {
+        let records = [
+            record("RAPIDHASH-64", Throughput::Bytes(64), 10.0),
+            record("RAPIDHASH-64", Throughput::Bytes(1024), 100.0),
+            record("XOR-128-AVX2", Throughput::Bytes(64), 5.0),
+            record("XOR-128-AVX2", Throughput::Bytes(1024), 50.0),
+            record("XOR-128-SSE2", Throughput::Bytes(64), 4.0),
+            record("XOR-128-SSE2", Throughput::Bytes(1024), 80.0),
+        ];
+        let table = RelativeTable::build(&records, &[
+            Reference::new("XXH3-64"),
+            Reference::new("XOR-128-*"),
+        ])
+        .expect("reference present");
+        assert_eq!(table.reference, "XOR-128-*");
+        assert_eq!(table.parameters, ["64 B", "1 KiB"]);
+        assert_eq!(table.rows[0].0, "XOR-128-AVX2");
+        let rapid = &table
+            .rows
+            .iter()
+            .find(|(f, _)| f == "RAPIDHASH-64")
+            .expect("row")
+            .1;
+        assert_eq!(rapid, &[Some(0.4), Some(0.5)]);
+
+        assert!(RelativeTable::build(&records, &[Reference::new("PCG64")]).is_none());
+    }
+
+    #[test]
+    fn heat_cells_and_geometric_mean() {
+        assert_eq!(heat_cell(Some(1.0)), "⬜ 1.00×");
+        assert_eq!(heat_cell(Some(2.5)), "🟩 2.50×");
+        assert_eq!(heat_cell(Some(0.1)), "🟥 0.10×");
+        assert_eq!(heat_cell(None), "-");
+        assert!((geometric_mean(&[Some(2.0), None, Some(8.0)]) - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn results_table_has_host_columns() {
+        let m4 = RelativeTable::build(
+            &[
+                record("XXH3-64", Throughput::Bytes(64), 10.0),
+                record("FxHash", Throughput::Bytes(64), 5.0),
+            ],
+            &[Reference::new("XXH3-64")],
+        )
+        .expect("table");
+        let ryzen = RelativeTable::build(
+            &[
+                record("XXH3-64", Throughput::Bytes(64), 10.0),
+                record("FxHash", Throughput::Bytes(64), 20.0),
+            ],
+            &[Reference::new("XXH3-64")],
+        )
+        .expect("table");
+        let mut out = String::new();
+        write_relative_results(&mut out, "Hash", &[("Apple M4", m4), ("Ryzen", ryzen)])
+            .expect("write");
+        assert!(out.contains("| Algorithm | Apple M4 | Ryzen |\n"));
+        assert!(out.contains("| `FxHash` | 🟩 2.00× | 🟨 0.50× |\n"));
+        assert!(out.contains("<details><summary>Ryzen, per size</summary>"));
+    }
+}
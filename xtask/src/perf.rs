@@ -0,0 +1,167 @@
+use std::{io::Write, path::Path};
+
+use crate::{
+    criterion::{BenchmarkRecord, Throughput, measurement_dir, read_group},
+    scope::Scope,
+    util::Result,
+};
+
+/// Hardware counter figures of one algorithm in one group, at the parameter with the
+/// fewest cycles per unit of work.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterSummary {
+    pub function: String,
+    pub group: String,
+    pub parameter: String,
+    /// `B`, `elem` or `bit`.
+    pub unit: &'static str,
+    pub cycles_per_unit: f64,
+    /// Instructions per cycle, when an `instructions` run exists.
+    pub ipc: Option<f64>,
+    /// Cache misses per 1024 units, when a `cache-misses` run exists.
+    pub misses_per_k_units: Option<f64>,
+}
+
+fn per_unit(record: &BenchmarkRecord) -> Option<(f64, &'static str)> {
+    let (amount, unit) = match record.throughput? {
+        Throughput::Bytes(n) | Throughput::BytesDecimal(n) => (n, "B"),
+        Throughput::Elements(n) => (n, "elem"),
+        Throughput::Bits(n) => (n, "bit"),
+    };
+    (amount > 0).then(|| (record.mean.point / amount as f64, unit))
+}
+
+fn same_case<'a>(
+    records: &'a [BenchmarkRecord],
+    record: &BenchmarkRecord,
+) -> Option<&'a BenchmarkRecord> {
+    records
+        .iter()
+        .find(|r| r.function == record.function && r.parameter == record.parameter)
+}
+
+pub fn counter_summaries(
+    group: &str,
+    cycles: &[BenchmarkRecord],
+    instructions: &[BenchmarkRecord],
+    cache_misses: &[BenchmarkRecord],
+) -> Vec<CounterSummary> {
+    let mut best: Vec<(f64, &'static str, &BenchmarkRecord)> = Vec::new();
+    for record in cycles {
+        let Some((cycles_per_unit, unit)) = per_unit(record) else {
+            continue;
+        };
+        match best
+            .iter_mut()
+            .find(|(_, _, r)| r.function == record.function)
+        {
+            Some(entry) if cycles_per_unit < entry.0 => *entry = (cycles_per_unit, unit, record),
+            Some(_) => {}
+            None => best.push((cycles_per_unit, unit, record)),
+        }
+    }
+
+    best.into_iter()
+        .map(|(cycles_per_unit, unit, record)| CounterSummary {
+            function: record.function.clone(),
+            group: group.to_owned(),
+            parameter: record.display_parameter(),
+            unit,
+            cycles_per_unit,
+            ipc: same_case(instructions, record)
+                .filter(|_| record.mean.point > 0.0)
+                .map(|instructions| instructions.mean.point / record.mean.point),
+            misses_per_k_units: same_case(cache_misses, record)
+                .and_then(per_unit)
+                .map(|(misses, _)| misses * 1024.0),
+        })
+        .collect()
+}
+
+/// Write the cycles / IPC table when `--measurement cycles` results exist next to
+/// `criterion_dir`.
+pub fn write_counter_markdown(
+    writer: &mut impl Write,
+    criterion_dir: &Path,
+    scope: Scope,
+) -> Result<()> {
+    let cycles_dir = measurement_dir(criterion_dir, "cycles");
+    let instructions_dir = measurement_dir(criterion_dir, "instructions");
+    let misses_dir = measurement_dir(criterion_dir, "cache-misses");
+
+    let mut rows = Vec::new();
+    for group in scope.summary_groups() {
+        rows.extend(counter_summaries(
+            group,
+            &read_group(&cycles_dir, group)?,
+            &read_group(&instructions_dir, group)?,
+            &read_group(&misses_dir, group)?,
+        ));
+    }
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "#### Cycles per Unit and IPC")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "User-space hardware counters at the size with the fewest cycles per unit. Unlike \
+         throughput, cycles per unit do not depend on clock speed or boost."
+    )?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "| Algorithm | Group | Cycles | IPC | Cache misses | At |"
+    )?;
+    writeln!(writer, "| --- | --- | --- | --- | --- | --- |")?;
+    for row in rows {
+        let ipc = row
+            .ipc
+            .map_or_else(|| "-".to_owned(), |ipc| format!("{ipc:.2}"));
+        let misses = row.misses_per_k_units.map_or_else(
+            || "-".to_owned(),
+            |misses| {
+                let per = match row.unit {
+                    "B" => "KiB",
+                    "elem" => "Kelem",
+                    _ => "Kbit",
+                };
+                format!("{misses:.3} / {per}")
+            },
+        );
+        writeln!(
+            writer,
+            "| `{}` | `{}` | {:.2} cycles/{} | {ipc} | {misses} | {} |",
+            row.function, row.group, row.cycles_per_unit, row.unit, row.parameter
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::counter_summaries;
+    use crate::criterion::{Throughput, tests::record};
+
+    #[test]
+    fn summaries_pair_counters_at_fewest_cycles_per_byte() {
+        let cycles = vec![
+            record("XXH3-64", Throughput::Bytes(16), 32.0),
+            record("XXH3-64", Throughput::Bytes(4096), 1024.0),
+            record("SHA2-256", Throughput::Bytes(16), 400.0),
+        ];
+        let instructions = vec![record("XXH3-64", Throughput::Bytes(4096), 3072.0)];
+        let misses = vec![record("XXH3-64", Throughput::Bytes(4096), 2.0)];
+
+        let rows = counter_summaries("g", &cycles, &instructions, &misses);
+        assert_eq!(rows.len(), 2);
+        let xxh3 = &rows[0];
+        assert_eq!(xxh3.parameter, "4 KiB");
+        assert_eq!(xxh3.cycles_per_unit, 0.25);
+        assert_eq!(xxh3.ipc, Some(3.0));
+        assert_eq!(xxh3.misses_per_k_units, Some(0.5));
+        assert_eq!(rows[1].ipc, None);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{fs, io::Write, path::Path, process::Command};
+
+use crate::{
+    cli::BenchOpts,
+    metadata::Ini,
+    util::{Result, load_last, save_last},
+};
+
+/// Where `xtask run` stores the placement of the last run, for `collect` to publish.
+const LAST_PLACEMENT_FILE: &str = "target/xtask/placement.ini";
+
+/// CPUs reserved with the `isolcpus=` kernel parameter.
+const ISOLATED_CPUS_FILE: &str = "/sys/devices/system/cpu/isolated";
+const ONLINE_CPUS_FILE: &str = "/sys/devices/system/cpu/online";
+
+/// Where and at which priority the benchmark binaries run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Placement {
+    /// CPUs the benchmark process is pinned to; `None` leaves affinity inherited.
+    pub cpus: Option<Vec<usize>>,
+    /// Whether `cpus` came from the kernel's isolated CPU list.
+    pub isolated: bool,
+    pub nice: Option<i32>,
+}
+
+impl Placement {
+    pub fn from_opts(opts: &BenchOpts) -> Result<Self> {
+        let (cpus, isolated) = if opts.isolate {
+            let raw = fs::read_to_string(ISOLATED_CPUS_FILE)
+                .map_err(|error| format!("cannot read {ISOLATED_CPUS_FILE}: {error}"))?;
+            let cpus = parse_cpu_list(&raw)?;
+            if cpus.is_empty() {
+                return Err(format!(
+                    "--isolate: no isolated CPUs in {ISOLATED_CPUS_FILE}; boot with `isolcpus=`"
+                )
+                .into());
+            }
+            (Some(cpus), true)
+        } else {
+            let cpus = opts.cpu.as_deref().map(parse_cpu_list).transpose()?;
+            (cpus, false)
+        };
+
+        if let Some(cpus) = &cpus
+            && let Ok(raw) = fs::read_to_string(ONLINE_CPUS_FILE)
+        {
+            let online = parse_cpu_list(&raw)?;
+            if let Some(cpu) = cpus.iter().find(|cpu| !online.contains(cpu)) {
+                return Err(format!("CPU {cpu} is not online (online: {})", raw.trim()).into());
+            }
+        }
+
+        Ok(Self {
+            cpus,
+            isolated,
+            nice: opts.nice,
+        })
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.cpus.is_none() && self.nice.is_none()
+    }
+
+    /// Make the spawned `cmd` (and everything it starts) run with this placement.
+    #[cfg(target_os = "linux")]
+    pub fn apply(&self, cmd: &mut Command) -> Result<()> {
+        use std::{io, os::unix::process::CommandExt};
+
+        let cpu_set = match &self.cpus {
+            Some(cpus) => {
+                // SAFETY: `cpu_set_t` is a plain bit mask; all-zero is the empty set.
+                let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+                for &cpu in cpus {
+                    if cpu >= libc::CPU_SETSIZE as usize {
+                        return Err(format!("CPU {cpu} exceeds CPU_SETSIZE").into());
+                    }
+                    // SAFETY: `cpu` is within the bounds of `set`.
+                    unsafe { libc::CPU_SET(cpu, &mut set) };
+                }
+                Some(set)
+            }
+            None => None,
+        };
+        let nice = self.nice;
+
+        // SAFETY: the closure only makes async-signal-safe syscalls and does not allocate.
+        unsafe {
+            cmd.pre_exec(move || {
+                if let Some(set) = &cpu_set
+                    && libc::sched_setaffinity(0, size_of::<libc::cpu_set_t>(), set) != 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+                if let Some(nice) = nice
+                    && libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(&self, _cmd: &mut Command) -> Result<()> {
+        if self.is_default() {
+            return Ok(());
+        }
+        Err("--cpu, --isolate and --nice are only supported on Linux".into())
+    }
+
+    /// `[placement]` section of `environment.ini`.
+    pub fn encode_ini(&self) -> String {
+        let mut out = String::from("[placement]\n");
+        out.push_str(&format!(
+            "cpus = {}\n",
+            self.cpus
+                .as_deref()
+                .map_or_else(|| "all".to_owned(), format_cpu_list)
+        ));
+        out.push_str(&format!("isolated = {}\n", self.isolated));
+        out.push_str(&format!(
+            "nice = {}\n",
+            self.nice
+                .map_or_else(|| "inherited".to_owned(), |nice| nice.to_string())
+        ));
+        out
+    }
+
+    /// Decode the `[placement]` section written by [`Self::encode_ini`].
+    pub fn decode_section(entries: &[(String, String)]) -> std::result::Result<Self, String> {
+        let mut placement = Self::default();
+        for (key, value) in entries {
+            let invalid = |error: String| format!("[placement] {key}: {error}");
+            match key.as_str() {
+                "cpus" if value == "all" => placement.cpus = None,
+                "cpus" => {
+                    placement.cpus =
+                        Some(parse_cpu_list(value).map_err(|error| invalid(error.to_string()))?);
+                }
+                "isolated" => {
+                    placement.isolated = value
+                        .parse()
+                        .map_err(|_| invalid(format!("`{value}` is not a boolean")))?;
+                }
+                "nice" if value == "inherited" => placement.nice = None,
+                "nice" => {
+                    placement.nice =
+                        Some(value.parse().map_err(|error| {
+                            invalid(format!("invalid nice `{value}`: {error}"))
+                        })?);
+                }
+                _ => return Err(format!("unknown key `{key}` in [placement]")),
+            }
+        }
+        Ok(placement)
+    }
+
+    pub fn decode_ini(input: &str) -> std::result::Result<Self, String> {
+        Self::decode_section(Ini::parse(input)?.entries("placement"))
+    }
+
+    pub fn write_markdown(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        if let Some(cpus) = &self.cpus {
+            writeln!(
+                writer,
+                "- Benchmark CPUs: {}{}",
+                format_cpu_list(cpus),
+                if self.isolated { " (isolated)" } else { "" }
+            )?;
+        }
+        if let Some(nice) = self.nice {
+            writeln!(writer, "- Benchmark niceness: {nice}")?;
+        }
+        Ok(())
+    }
+
+    pub fn save_last(&self, workspace_root: &Path, stamp: &str) -> Result<()> {
+        save_last(
+            workspace_root,
+            LAST_PLACEMENT_FILE,
+            stamp,
+            &self.encode_ini(),
+        )
+    }
+
+    /// Placement of the `xtask run` that wrote the criterion output `stamp`, if any.
+    pub fn load_last(workspace_root: &Path, stamp: Option<&str>) -> Option<Self> {
+        let raw = load_last(workspace_root, LAST_PLACEMENT_FILE, stamp)?;
+        Self::decode_ini(&raw).ok()
+    }
+}
+
+/// Parse a kernel CPU list such as `0-3,8,10-11`. An empty list is valid.
+pub fn parse_cpu_list(input: &str) -> Result<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in input.trim().split(',').map(str::trim) {
+        if part.is_empty() {
+            continue;
+        }
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|error| format!("invalid CPU list `{input}`: {error}"))
+        };
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("invalid CPU range `{part}`").into());
+                }
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(parse(part)?),
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// Inverse of [`parse_cpu_list`], collapsing consecutive CPUs into ranges.
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut parts = Vec::new();
+    let mut iter = cpus.iter().copied().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().expect("peeked");
+        }
+        parts.push(if start == end {
+            start.to_string()
+        } else {
+            format!("{start}-{end}")
+        });
+    }
+    parts.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Placement, format_cpu_list, parse_cpu_list};
+
+    #[test]
+    fn cpu_list_roundtrip() {
+        let cpus = parse_cpu_list("8, 0-3,10-11,2\n").expect("parse");
+        assert_eq!(cpus, [0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(format_cpu_list(&cpus), "0-3,8,10-11");
+        assert!(parse_cpu_list("").expect("empty").is_empty());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("a").is_err());
+    }
+
+    #[test]
+    fn placement_ini_roundtrip() {
+        let placement = Placement {
+            cpus: Some(vec![2, 3]),
+            isolated: true,
+            nice: Some(-5),
+        };
+        let decoded = Placement::decode_ini(&placement.encode_ini()).expect("decode");
+        assert_eq!(decoded, placement);
+        let decoded = Placement::decode_ini(&Placement::default().encode_ini()).expect("decode");
+        assert!(decoded.is_default());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn apply_pins_child_process() {
+        // Containers and cgroups may not allow CPU 0; pin to the first CPU we may use.
+        // SAFETY: `cpu_set_t` is a plain bit mask; all-zero is the empty set.
+        let mut allowed: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        // SAFETY: `allowed` is a valid `cpu_set_t` of the given size.
+        let result =
+            unsafe { libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &mut allowed) };
+        assert_eq!(result, 0, "sched_getaffinity");
+        let cpu = (0..libc::CPU_SETSIZE as usize)
+            // SAFETY: `cpu` is within the bounds of `allowed`.
+            .find(|&cpu| unsafe { libc::CPU_ISSET(cpu, &allowed) })
+            .expect("at least one allowed CPU");
+        let placement = Placement {
+            cpus: Some(vec![cpu]),
+            ..Placement::default()
+        };
+        let mut cmd = std::process::Command::new("grep");
+        cmd.args(["Cpus_allowed_list", "/proc/self/status"]);
+        placement.apply(&mut cmd).expect("apply");
+        let output = cmd.output().expect("spawn grep");
+        let status = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(
+            status.split_whitespace().last(),
+            Some(cpu.to_string().as_str())
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    metadata::Ini,
+    util::{Result, load_last, save_last},
+};
+
+/// Where `xtask run` stores the checks of the last run, for `collect` to publish.
+const LAST_REPORT_FILE: &str = "target/xtask/preflight.ini";
+
+/// 1-minute load average above which other work is assumed to disturb the run.
+const LOAD_WARN_THRESHOLD: f64 = 1.0;
+
+/// One inspected property of the machine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// INI key, e.g. `governor`.
+    pub key: &'static str,
+    pub value: String,
+    /// Why the value may distort results, if it does.
+    pub warning: Option<String>,
+}
+
+/// Findings of the pre-flight step that runs before `cargo bench`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreflightReport {
+    pub checks: Vec<Check>,
+}
+
+impl PreflightReport {
+    pub fn detect() -> Self {
+        Self::detect_in(Path::new("/"))
+    }
+
+    /// Inspect `/sys` and `/proc` below `root`. Checks whose files are missing (for
+    /// example outside Linux or in VMs) are reported as `unknown` without a warning.
+    fn detect_in(root: &Path) -> Self {
+        let cpu_dir = root.join("sys/devices/system/cpu");
+        Self {
+            checks: vec![
+                check_governor(&cpu_dir),
+                check_turbo(&cpu_dir),
+                check_smt(&cpu_dir),
+                check_load(&root.join("proc/loadavg")),
+                check_thermal_throttle(&cpu_dir),
+                check_power(&root.join("sys/class/power_supply")),
+            ],
+        }
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.checks
+            .iter()
+            .filter_map(|check| check.warning.as_deref())
+    }
+
+    /// Print warnings, and fail in `strict` mode when there are any.
+    pub fn enforce(&self, strict: bool) -> Result<()> {
+        let warnings: Vec<&str> = self.warnings().collect();
+        for warning in &warnings {
+            eprintln!("warning: {warning}");
+        }
+        if strict && !warnings.is_empty() {
+            return Err(format!(
+                "pre-flight checks failed with --strict ({} warning(s))",
+                warnings.len()
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// `[preflight]` section of `environment.ini`.
+    pub fn encode_ini(&self) -> String {
+        let mut out = String::from("[preflight]\n");
+        for check in &self.checks {
+            out.push_str(&format!("{} = {}\n", check.key, check.value));
+            if let Some(warning) = &check.warning {
+                out.push_str(&format!("{}_warning = {warning}\n", check.key));
+            }
+        }
+        out
+    }
+
+    /// Decode the `[preflight]` section written by [`Self::encode_ini`].
+    pub fn decode_section(entries: &[(String, String)]) -> std::result::Result<Self, String> {
+        let mut checks: Vec<Check> = Vec::new();
+        for (key, value) in entries {
+            let value = value.clone();
+            if let Some(check) = key
+                .strip_suffix("_warning")
+                .and_then(|key| checks.iter_mut().find(|check| check.key == key))
+            {
+                check.warning = Some(value);
+            } else if let Some(key) = CHECK_KEYS.iter().find(|known| *known == key) {
+                checks.push(Check {
+                    key,
+                    value,
+                    warning: None,
+                });
+            } else {
+                return Err(format!("unknown key `{key}` in [preflight]"));
+            }
+        }
+        Ok(Self { checks })
+    }
+
+    pub fn decode_ini(input: &str) -> std::result::Result<Self, String> {
+        Self::decode_section(Ini::parse(input)?.entries("preflight"))
+    }
+
+    pub fn write_markdown(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writeln!(writer, "### Pre-flight Checks")?;
+        writeln!(writer)?;
+        for check in &self.checks {
+            match &check.warning {
+                Some(warning) => writeln!(writer, "- {}: {} ⚠ {warning}", check.key, check.value)?,
+                None => writeln!(writer, "- {}: {}", check.key, check.value)?,
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save_last(&self, workspace_root: &Path, stamp: &str) -> Result<()> {
+        save_last(workspace_root, LAST_REPORT_FILE, stamp, &self.encode_ini())
+    }
+
+    /// Checks of the `xtask run` that wrote the criterion output `stamp`, if any.
+    pub fn load_last(workspace_root: &Path, stamp: Option<&str>) -> Option<Self> {
+        let raw = load_last(workspace_root, LAST_REPORT_FILE, stamp)?;
+        Self::decode_ini(&raw).ok()
+    }
+}
+
+const CHECK_KEYS: &[&str] = &[
+    "governor",
+    "turbo",
+    "smt",
+    "load_average",
+    "thermal_throttle_events",
+    "power",
+];
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(fs::read_to_string(path).ok()?.trim().to_owned())
+}
+
+fn cpu_dirs(cpu_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(cpu_dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn unknown(key: &'static str) -> Check {
+    Check {
+        key,
+        value: "unknown".to_owned(),
+        warning: None,
+    }
+}
+
+fn check_governor(cpu_dir: &Path) -> Check {
+    let mut governors: Vec<String> = cpu_dirs(cpu_dir)
+        .iter()
+        .filter_map(|dir| read_trimmed(&dir.join("cpufreq/scaling_governor")))
+        .collect();
+    governors.sort();
+    governors.dedup();
+    if governors.is_empty() {
+        return unknown("governor");
+    }
+    let value = governors.join(",");
+    let warning = governors.iter().any(|g| g != "performance").then(|| {
+        format!("CPU frequency governor is `{value}`, not `performance`; clock speed may vary")
+    });
+    Check {
+        key: "governor",
+        value,
+        warning,
+    }
+}
+
+fn check_turbo(cpu_dir: &Path) -> Check {
+    // intel_pstate inverts the flag; acpi-cpufreq and amd-pstate use `cpufreq/boost`.
+    let enabled = match read_trimmed(&cpu_dir.join("intel_pstate/no_turbo")) {
+        Some(no_turbo) => no_turbo == "0",
+        None => match read_trimmed(&cpu_dir.join("cpufreq/boost")) {
+            Some(boost) => boost == "1",
+            None => return unknown("turbo"),
+        },
+    };
+    Check {
+        key: "turbo",
+        value: if enabled { "enabled" } else { "disabled" }.to_owned(),
+        warning: enabled.then(|| {
+            "turbo/boost is enabled; clock speed depends on load and temperature".to_owned()
+        }),
+    }
+}
+
+fn check_smt(cpu_dir: &Path) -> Check {
+    let Some(active) = read_trimmed(&cpu_dir.join("smt/active")) else {
+        return unknown("smt");
+    };
+    let active = active == "1";
+    Check {
+        key: "smt",
+        value: if active { "active" } else { "inactive" }.to_owned(),
+        warning: active.then(|| {
+            "SMT is active; work on a sibling thread shares the benchmarked core".to_owned()
+        }),
+    }
+}
+
+fn check_load(loadavg: &Path) -> Check {
+    let Some(load) =
+        read_trimmed(loadavg).and_then(|raw| raw.split_whitespace().next()?.parse::<f64>().ok())
+    else {
+        return unknown("load_average");
+    };
+    Check {
+        key: "load_average",
+        value: format!("{load:.2}"),
+        warning: (load > LOAD_WARN_THRESHOLD)
+            .then(|| format!("1-minute load average is {load:.2}; other work is running")),
+    }
+}
+
+fn check_thermal_throttle(cpu_dir: &Path) -> Check {
+    let counts: Vec<u64> = cpu_dirs(cpu_dir)
+        .iter()
+        .filter_map(|dir| {
+            read_trimmed(&dir.join("thermal_throttle/core_throttle_count"))?
+                .parse()
+                .ok()
+        })
+        .collect();
+    if counts.is_empty() {
+        return unknown("thermal_throttle_events");
+    }
+    let total: u64 = counts.iter().sum();
+    Check {
+        key: "thermal_throttle_events",
+        value: total.to_string(),
+        warning: (total > 0)
+            .then(|| format!("{total} thermal throttling event(s) since boot; check cooling")),
+    }
+}
+
+fn check_power(power_supply_dir: &Path) -> Check {
+    let Ok(entries) = fs::read_dir(power_supply_dir) else {
+        return unknown("power");
+    };
+    let mut has_battery = false;
+    let mut mains_online = false;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        match read_trimmed(&dir.join("type")).as_deref() {
+            Some("Battery") => has_battery = true,
+            Some("Mains") => {
+                mains_online |= read_trimmed(&dir.join("online")).as_deref() == Some("1")
+            }
+            _ => {}
+        }
+    }
+    let (value, warning) = match (has_battery, mains_online) {
+        (_, true) => ("ac", None),
+        (true, false) => (
+            "battery",
+            Some("running on battery; power management may lower clock speed".to_owned()),
+        ),
+        (false, false) => return unknown("power"),
+    };
+    Check {
+        key: "power",
+        value: value.to_owned(),
+        warning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::PreflightReport;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(path, content).expect("write file");
+    }
+
+    #[test]
+    fn detect_reports_noisy_machine() {
+        let root = temp_dir("preflight-noisy");
+        write(
+            &root,
+            "sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+            "powersave\n",
+        );
+        write(
+            &root,
+            "sys/devices/system/cpu/cpu1/cpufreq/scaling_governor",
+            "performance\n",
+        );
+        write(
+            &root,
+            "sys/devices/system/cpu/cpu0/thermal_throttle/core_throttle_count",
+            "3\n",
+        );
+        write(&root, "sys/devices/system/cpu/intel_pstate/no_turbo", "0\n");
+        write(&root, "sys/devices/system/cpu/smt/active", "1\n");
+        write(&root, "proc/loadavg", "2.50 1.00 0.50 1/100 42\n");
+        write(&root, "sys/class/power_supply/BAT0/type", "Battery\n");
+        write(&root, "sys/class/power_supply/AC/type", "Mains\n");
+        write(&root, "sys/class/power_supply/AC/online", "0\n");
+
+        let report = PreflightReport::detect_in(&root);
+        let values: Vec<(&str, &str)> = report
+            .checks
+            .iter()
+            .map(|check| (check.key, check.value.as_str()))
+            .collect();
+        assert_eq!(values, [
+            ("governor", "performance,powersave"),
+            ("turbo", "enabled"),
+            ("smt", "active"),
+            ("load_average", "2.50"),
+            ("thermal_throttle_events", "3"),
+            ("power", "battery"),
+        ]);
+        assert_eq!(report.warnings().count(), 6);
+        assert!(report.enforce(true).is_err());
+        assert!(report.enforce(false).is_ok());
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn detect_quiet_machine_and_missing_files() {
+        let root = temp_dir("preflight-quiet");
+        write(
+            &root,
+            "sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+            "performance\n",
+        );
+        write(&root, "sys/devices/system/cpu/cpufreq/boost", "0\n");
+        write(&root, "proc/loadavg", "0.05 0.10 0.10 1/100 42\n");
+
+        let report = PreflightReport::detect_in(&root);
+        assert_eq!(report.warnings().count(), 0);
+        assert!(report.enforce(true).is_ok());
+        let smt = report.checks.iter().find(|c| c.key == "smt").expect("smt");
+        assert_eq!(smt.value, "unknown");
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn ini_roundtrip() {
+        let root = temp_dir("preflight-ini");
+        write(&root, "sys/devices/system/cpu/smt/active", "1\n");
+        let report = PreflightReport::detect_in(&root);
+        let decoded = PreflightReport::decode_ini(&report.encode_ini()).expect("decode");
+        assert_eq!(decoded, report);
+
+        // Only the collect of the criterion output the run wrote picks the report up.
+        report.save_last(&root, "12 cases, newest 1").expect("save");
+        assert_eq!(
+            PreflightReport::load_last(&root, Some("12 cases, newest 1")),
+            Some(report)
+        );
+        assert_eq!(
+            PreflightReport::load_last(&root, Some("12 cases, newest 2")),
+            None
+        );
+        assert_eq!(PreflightReport::load_last(&root, None), None);
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
@@ -0,0 +1,507 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    cli::{OutlierPolicy, ReanalyzeOpts},
+    criterion::{BenchmarkRecord, Estimate, Sample, read_group_samples},
+    runs::{SummaryEntry, list_runs, read_summary},
+    samples::{SAMPLES_ARCHIVE, extract_samples},
+    util::{Result, workspace_root},
+};
+
+/// Where the samples of a run are unpacked while it is reanalyzed.
+const EXTRACT_DIR: &str = "target/xtask/reanalyze";
+/// Full estimates of the last `reanalyze --write` of a run, next to its `summary.json`,
+/// which keeps criterion's own estimates.
+pub const REANALYSIS_FILE: &str = "reanalysis.json";
+
+/// How estimates are computed from samples.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct Method {
+    confidence: f64,
+    resamples: usize,
+    seed: u64,
+    outliers: &'static str,
+    #[serde(skip)]
+    policy: OutlierPolicy,
+}
+
+/// Estimates of one case, recomputed from its samples.
+#[derive(Debug, Clone, Serialize)]
+struct Analysis {
+    scope: String,
+    group: String,
+    function: String,
+    parameter: Option<String>,
+    /// Measurements the estimates are based on, after dropping outliers.
+    samples: usize,
+    /// Per-iteration time, in the unit of the samples.
+    mean: Estimate,
+    median: Estimate,
+    std_dev: f64,
+    /// Time per iteration fitted over all measurements; only for linear sampling.
+    slope: Option<Estimate>,
+    outliers: Outliers,
+}
+
+/// Tukey's fences over per-iteration times, as criterion classifies them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+struct Outliers {
+    low_severe: usize,
+    low_mild: usize,
+    high_mild: usize,
+    high_severe: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Reanalysis {
+    #[serde(flatten)]
+    method: Method,
+    cases: Vec<Analysis>,
+}
+
+pub fn reanalyze_results(opts: &ReanalyzeOpts) -> Result<()> {
+    if !(opts.confidence > 0.0 && opts.confidence < 1.0) {
+        return Err(format!("--confidence {} is not between 0 and 1", opts.confidence).into());
+    }
+    if opts.resamples == 0 {
+        return Err("--resamples must be at least 1".into());
+    }
+    let method = Method {
+        confidence: opts.confidence,
+        resamples: opts.resamples,
+        seed: opts.seed,
+        outliers: match opts.outliers {
+            OutlierPolicy::Keep => "keep",
+            OutlierPolicy::DropSevere => "drop-severe",
+            OutlierPolicy::DropAll => "drop-all",
+        },
+        policy: opts.outliers,
+    };
+    let workspace_root = workspace_root();
+    let runs = select_runs(
+        &workspace_root.join("results"),
+        opts.host.as_deref(),
+        opts.run.as_deref(),
+    )?;
+    let extract_dir = workspace_root.join(EXTRACT_DIR);
+    let mut reanalyzed = 0;
+    for (host, run, run_dir) in &runs {
+        if !extract_samples(run_dir, &extract_dir)? {
+            continue;
+        }
+        let result = reanalyze_run(
+            &mut io::stdout().lock(),
+            &format!("{host} {run}"),
+            run_dir,
+            &extract_dir,
+            method,
+            opts.write,
+        );
+        fs::remove_dir_all(&extract_dir)?;
+        result?;
+        reanalyzed += 1;
+    }
+    if reanalyzed == 0 {
+        return Err(format!(
+            "none of the {} selected run(s) has {SAMPLES_ARCHIVE}",
+            runs.len()
+        )
+        .into());
+    }
+    if reanalyzed < runs.len() {
+        eprintln!(
+            "note: skipped {} run(s) collected without {SAMPLES_ARCHIVE}",
+            runs.len() - reanalyzed
+        );
+    }
+    Ok(())
+}
+
+/// `(host, run id, run directory)` of every run matching the filters.
+fn select_runs(
+    results_root: &Path,
+    host: Option<&str>,
+    run: Option<&str>,
+) -> Result<Vec<(String, String, PathBuf)>> {
+    let mut selected = Vec::new();
+    if results_root.is_dir() {
+        for entry in fs::read_dir(results_root)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !entry.path().is_dir() || host.is_some_and(|host| host != name) {
+                continue;
+            }
+            for (id, dir) in list_runs(&entry.path())? {
+                if run.is_none_or(|run| run == id) {
+                    selected.push((name.clone(), id, dir));
+                }
+            }
+        }
+    }
+    selected.sort();
+    if selected.is_empty() {
+        let filter = match (host, run) {
+            (Some(host), Some(run)) => format!(" run `{run}` of `{host}`"),
+            (Some(host), None) => format!(" runs of `{host}`"),
+            (None, Some(run)) => format!(" run `{run}`"),
+            (None, None) => " runs".to_owned(),
+        };
+        return Err(format!("no collected{filter} in {}", results_root.display()).into());
+    }
+    Ok(selected)
+}
+
+/// Recompute the summary of `run_dir` from the criterion directory `samples_dir` and print
+/// it against the recorded one; with `write`, save the estimates to [`REANALYSIS_FILE`].
+fn reanalyze_run(
+    writer: &mut impl Write,
+    title: &str,
+    run_dir: &Path,
+    samples_dir: &Path,
+    method: Method,
+    write: bool,
+) -> Result<()> {
+    let entries = read_summary(run_dir)?
+        .ok_or_else(|| format!("{} has samples but no summary.json", run_dir.display()))?;
+    let mut groups: Vec<&str> = entries.iter().map(|entry| entry.group.as_str()).collect();
+    groups.dedup();
+    let mut cases = Vec::new();
+    for group in groups {
+        cases.extend(
+            read_group_samples(samples_dir, group)?
+                .into_iter()
+                .map(|(record, sample)| (group.to_owned(), record, sample)),
+        );
+    }
+
+    let mut rng = SplitMix64(method.seed);
+    let mut analyses = Vec::new();
+    writeln!(writer, "# {title}")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "| Group | Benchmark | Parameter | Recorded | Reanalyzed | {:.0}% CI | Change | Outliers |",
+        method.confidence * 100.0
+    )?;
+    writeln!(writer, "| --- | --- | --- | --- | --- | --- | --- | --- |")?;
+    for entry in &entries {
+        let Some((_, _, sample)) = cases.iter().find(|(group, record, _)| {
+            *group == entry.group
+                && record.function == entry.record.function
+                && record.parameter == entry.record.parameter
+        }) else {
+            continue;
+        };
+        let analysis = analyze(entry, sample, method, &mut rng)
+            .map_err(|error| format!("{} {}: {error}", entry.group, entry.record.function))?;
+        let reanalyzed = BenchmarkRecord {
+            mean: analysis.mean,
+            ..entry.record.clone()
+        };
+        let rate = |record: &BenchmarkRecord| {
+            record
+                .display_throughput()
+                .unwrap_or_else(|| format!("{:.2} ns", record.mean.point))
+        };
+        let outliers = analysis.outliers;
+        writeln!(
+            writer,
+            "| {} | {} | {} | {} | {} | {:.2} - {:.2} ns | {:+.2}% | {} |",
+            entry.group,
+            entry.record.function,
+            entry.record.display_parameter(),
+            rate(&entry.record),
+            rate(&reanalyzed),
+            analysis.mean.lower,
+            analysis.mean.upper,
+            (analysis.mean.point / entry.record.mean.point - 1.0) * 100.0,
+            if outliers == Outliers::default() {
+                "-".to_owned()
+            } else {
+                format!(
+                    "{} mild, {} severe",
+                    outliers.low_mild + outliers.high_mild,
+                    outliers.low_severe + outliers.high_severe
+                )
+            },
+        )?;
+        analyses.push(analysis);
+    }
+    writeln!(writer)?;
+
+    if write {
+        let reanalysis = Reanalysis {
+            method,
+            cases: analyses,
+        };
+        let json = serde_json::to_string_pretty(&reanalysis)
+            .map_err(|error| format!("failed to encode reanalysis: {error}"))?;
+        fs::write(run_dir.join(REANALYSIS_FILE), json)?;
+    }
+    Ok(())
+}
+
+fn analyze(
+    entry: &SummaryEntry,
+    sample: &Sample,
+    method: Method,
+    rng: &mut SplitMix64,
+) -> Result<Analysis> {
+    if sample.iters.len() != sample.times.len() || sample.iters.is_empty() {
+        return Err("sample.json has mismatched or empty iters and times".into());
+    }
+    let averages: Vec<f64> = sample
+        .iters
+        .iter()
+        .zip(&sample.times)
+        .map(|(iters, time)| time / iters)
+        .collect();
+    let mut sorted = averages.clone();
+    sorted.sort_by(f64::total_cmp);
+    let (q1, q3) = (percentile(&sorted, 25.0), percentile(&sorted, 75.0));
+    let iqr = q3 - q1;
+    let mut outliers = Outliers::default();
+    let mut kept = Vec::new();
+    for (index, &average) in averages.iter().enumerate() {
+        let severe = average < q1 - 3.0 * iqr || average > q3 + 3.0 * iqr;
+        let mild = !severe && (average < q1 - 1.5 * iqr || average > q3 + 1.5 * iqr);
+        match (severe, mild, average < q1) {
+            (true, _, true) => outliers.low_severe += 1,
+            (true, _, false) => outliers.high_severe += 1,
+            (_, true, true) => outliers.low_mild += 1,
+            (_, true, false) => outliers.high_mild += 1,
+            _ => {}
+        }
+        let dropped = match method.policy {
+            OutlierPolicy::Keep => false,
+            OutlierPolicy::DropSevere => severe,
+            OutlierPolicy::DropAll => severe || mild,
+        };
+        if !dropped {
+            kept.push(index);
+        }
+    }
+    if kept.len() < 2 {
+        return Err("fewer than two measurements left after dropping outliers".into());
+    }
+
+    let pairs: Vec<(f64, f64)> = kept
+        .iter()
+        .map(|&index| (sample.iters[index], sample.times[index]))
+        .collect();
+    let linear = sample.sampling_mode == "Linear";
+    let mut resampled = vec![(0.0, 0.0); pairs.len()];
+    let mut scratch = Vec::with_capacity(pairs.len());
+    let mut distributions = [(); 3].map(|()| Vec::with_capacity(method.resamples));
+    for _ in 0..method.resamples {
+        for slot in &mut resampled {
+            *slot = pairs[rng.below(pairs.len())];
+        }
+        let statistics = statistics(&resampled, &mut scratch);
+        for (distribution, statistic) in distributions.iter_mut().zip(statistics) {
+            distribution.push(statistic);
+        }
+    }
+    let estimate = |point: f64, distribution: &mut Vec<f64>| {
+        distribution.sort_by(f64::total_cmp);
+        let tail = (1.0 - method.confidence) / 2.0 * 100.0;
+        Estimate {
+            point,
+            lower: percentile(distribution, tail),
+            upper: percentile(distribution, 100.0 - tail),
+        }
+    };
+    let [means, medians, slopes] = &mut distributions;
+    let [mean_point, median_point, slope_point] = statistics(&pairs, &mut scratch);
+    let variance = pairs
+        .iter()
+        .map(|(iters, time)| (time / iters - mean_point).powi(2))
+        .sum::<f64>()
+        / (pairs.len() - 1) as f64;
+    Ok(Analysis {
+        scope: entry.scope.clone(),
+        group: entry.group.clone(),
+        function: entry.record.function.clone(),
+        parameter: entry.record.parameter.clone(),
+        samples: pairs.len(),
+        mean: estimate(mean_point, means),
+        median: estimate(median_point, medians),
+        std_dev: variance.sqrt(),
+        slope: linear.then(|| estimate(slope_point, slopes)),
+        outliers,
+    })
+}
+
+/// Mean and median per-iteration time of `(iters, time)` measurements, and the
+/// least-squares slope through the origin of time over iterations.
+fn statistics(pairs: &[(f64, f64)], averages: &mut Vec<f64>) -> [f64; 3] {
+    averages.clear();
+    let (mut xy, mut xx) = (0.0, 0.0);
+    for &(iters, time) in pairs {
+        averages.push(time / iters);
+        xy += iters * time;
+        xx += iters * iters;
+    }
+    let mean = averages.iter().sum::<f64>() / averages.len() as f64;
+    // The 50th percentile without sorting: the middle value, or halfway between the two.
+    let middle = (averages.len() - 1) / 2;
+    let (_, &mut low, above) = averages.select_nth_unstable_by(middle, f64::total_cmp);
+    let median = match above.iter().copied().min_by(f64::total_cmp) {
+        Some(high) if averages.len().is_multiple_of(2) => low + (high - low) * 0.5,
+        _ => low,
+    };
+    [mean, median, xy / xx]
+}
+
+/// Linearly interpolated percentile of sorted values, as criterion computes them.
+fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    match sorted.get(below + 1) {
+        Some(above) => sorted[below] + (above - sorted[below]) * (rank - below as f64),
+        None => sorted[below],
+    }
+}
+
+/// Small deterministic generator for bootstrap resampling, so reanalyses are repeatable.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index below `n`.
+    fn below(&mut self, n: usize) -> usize {
+        ((u128::from(self.next()) * n as u128) >> 64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{Method, REANALYSIS_FILE, percentile, reanalyze_run};
+    use crate::{
+        cli::OutlierPolicy,
+        criterion::tests::{write_case, write_sample},
+        runs::{read_summary, write_summary},
+        samples::{SAMPLES_ARCHIVE, archive_samples, extract_samples},
+    };
+
+    #[test]
+    fn reanalyze_stored_samples() {
+        let root = temp_dir("reanalyze");
+        let criterion = root.join("criterion");
+        let run_dir = root.join("run");
+        fs::create_dir_all(&run_dir).expect("create run dir");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            60.0,
+        );
+        // 50 ns per iteration, but one measurement took ten times as long.
+        let iters: Vec<f64> = (1..=10).map(|n| f64::from(n) * 100.0).collect();
+        let mut times: Vec<f64> = iters.iter().map(|iters| iters * 50.0).collect();
+        times[9] *= 10.0;
+        write_sample(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            &iters,
+            &times,
+        );
+        write_summary(&run_dir, &criterion).expect("write summary");
+        assert!(archive_samples(&criterion, &run_dir).expect("archive samples"));
+        assert!(run_dir.join(SAMPLES_ARCHIVE).is_file());
+
+        let samples_dir = root.join("samples");
+        assert!(extract_samples(&run_dir, &samples_dir).expect("extract samples"));
+        let mut method = Method {
+            confidence: 0.95,
+            resamples: 1000,
+            seed: 7,
+            outliers: "keep",
+            policy: OutlierPolicy::Keep,
+        };
+        let mut table = Vec::new();
+        reanalyze_run(
+            &mut table,
+            "host run",
+            &run_dir,
+            &samples_dir,
+            method,
+            false,
+        )
+        .expect("reanalyze");
+        let table = String::from_utf8(table).expect("utf-8");
+        assert!(
+            table.contains("| non_cryptographic_hash | XXH3-64 | 1 KiB |"),
+            "{table}"
+        );
+        assert!(table.contains("| +58.33% | 0 mild, 1 severe |"), "{table}");
+        let summary = read_summary(&run_dir).expect("read").expect("summary");
+        assert_eq!(summary[0].record.mean.point, 60.0);
+        assert!(!run_dir.join(REANALYSIS_FILE).exists());
+
+        method.outliers = "drop-severe";
+        method.policy = OutlierPolicy::DropSevere;
+        reanalyze_run(
+            &mut Vec::new(),
+            "host run",
+            &run_dir,
+            &samples_dir,
+            method,
+            true,
+        )
+        .expect("reanalyze and write");
+        // Criterion's estimates stay in the summary, so later runs compare against them.
+        let summary = read_summary(&run_dir).expect("read").expect("summary");
+        assert_eq!(summary[0].record.mean.point, 60.0);
+        let reanalysis: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(run_dir.join(REANALYSIS_FILE)).expect("read reanalysis"),
+        )
+        .expect("parse reanalysis");
+        assert_eq!(reanalysis["outliers"], "drop-severe");
+        assert_eq!(reanalysis["cases"][0]["samples"], 9);
+        assert_eq!(reanalysis["cases"][0]["mean"]["point"], 50.0);
+        assert_eq!(reanalysis["cases"][0]["slope"]["point"], 50.0);
+        assert_eq!(reanalysis["cases"][0]["outliers"]["high_severe"], 1);
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn percentile_interpolates() {
+        let sorted = [1.0, 2.0, 4.0, 8.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 3.0);
+        assert_eq!(percentile(&sorted, 100.0), 8.0);
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
@@ -0,0 +1,205 @@
+use std::{fs, path::Path};
+
+use serde::Serialize;
+
+use crate::{
+    cli::ReportOpts,
+    criterion::Throughput,
+    environment::read_metadata_ini,
+    results::list_hosts,
+    runs::{SUMMARY_FILE, read_summary},
+    util::{Result, workspace_root},
+};
+
+/// Page skeleton with inline CSS and JS; `/*REPORT_DATA*/` is replaced by the JSON data.
+const TEMPLATE: &str = include_str!("report.html");
+const DATA_PLACEHOLDER: &str = "/*REPORT_DATA*/";
+
+#[derive(Debug, Serialize)]
+struct ReportData {
+    hosts: Vec<ReportHost>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportHost {
+    id: String,
+    title: String,
+    /// Published run, relative to `results/`.
+    run: String,
+    environment: Vec<ReportSection>,
+    records: Vec<ReportRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportSection {
+    name: String,
+    entries: Vec<(String, String)>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportRecord {
+    scope: String,
+    group: String,
+    function: String,
+    /// Bytes, elements or bits per iteration, the x axis of the charts.
+    size: u64,
+    size_label: String,
+    /// `bytes`, `elements` or `bits`.
+    unit: &'static str,
+    /// Units per second at the mean.
+    rate: f64,
+    mean_ns: f64,
+    lower_ns: f64,
+    upper_ns: f64,
+}
+
+pub fn write_report(opts: &ReportOpts) -> Result<()> {
+    let workspace_root = workspace_root();
+    let out_dir = workspace_root.join(&opts.out);
+    let index = write_report_site(workspace_root, &out_dir)?;
+    println!("{}", index.display());
+    Ok(())
+}
+
+/// Build the site into `out_dir` and return the path of its `index.html`.
+fn write_report_site(workspace_root: &Path, out_dir: &Path) -> Result<std::path::PathBuf> {
+    let data = collect_report_data(workspace_root)?;
+    let json = serde_json::to_string(&data)
+        .map_err(|error| format!("failed to encode report data: {error}"))?;
+    // Keep `</script>` inside strings from closing the data script early.
+    let json = json.replace("</", "<\\/");
+
+    fs::create_dir_all(out_dir)?;
+    let index = out_dir.join("index.html");
+    fs::write(&index, TEMPLATE.replace(DATA_PLACEHOLDER, &json))?;
+    Ok(index)
+}
+
+fn collect_report_data(workspace_root: &Path) -> Result<ReportData> {
+    let results_root = workspace_root.join("results");
+    let mut hosts = Vec::new();
+    let mut skipped = 0;
+    for host in list_hosts(workspace_root)? {
+        let run_dir = results_root.join(&host.dir);
+        let Some(summary) = read_summary(&run_dir)? else {
+            skipped += 1;
+            continue;
+        };
+        let environment = read_metadata_ini(&run_dir)
+            .unwrap_or_default()
+            .sections
+            .into_iter()
+            .map(|(name, entries)| ReportSection { name, entries })
+            .collect();
+        let records = summary
+            .into_iter()
+            .filter_map(|entry| {
+                let throughput = entry.record.throughput?;
+                Some(ReportRecord {
+                    size: throughput.amount(),
+                    size_label: entry.record.display_parameter(),
+                    unit: match throughput {
+                        Throughput::Bytes(_) | Throughput::BytesDecimal(_) => "bytes",
+                        Throughput::Elements(_) => "elements",
+                        Throughput::Bits(_) => "bits",
+                    },
+                    rate: entry.record.throughput_per_sec()?,
+                    mean_ns: entry.record.mean.point,
+                    lower_ns: entry.record.mean.lower,
+                    upper_ns: entry.record.mean.upper,
+                    scope: entry.scope,
+                    group: entry.group,
+                    function: entry.record.function,
+                })
+            })
+            .collect();
+        hosts.push(ReportHost {
+            id: host.id,
+            title: host.title,
+            run: host.dir,
+            environment,
+            records,
+        });
+    }
+    if skipped > 0 {
+        eprintln!("note: skipped {skipped} host(s) whose published run has no {SUMMARY_FILE}");
+    }
+    if hosts.iter().all(|host| host.records.is_empty()) {
+        return Err(format!(
+            "no published run has results in {SUMMARY_FILE}; collect the runs again with \
+             `cargo xc` to write it"
+        )
+        .into());
+    }
+    Ok(ReportData { hosts })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::write_report_site;
+    use crate::{criterion::tests::write_case, runs::write_summary};
+
+    #[test]
+    fn report_is_self_contained() {
+        let root = temp_dir("report");
+        let run_dir = root.join("results/apple-m4-macos/runs/2026-03-01T00-00-00Z");
+        fs::create_dir_all(run_dir.join("charts")).expect("create charts dir");
+        fs::write(
+            run_dir.join("environment.ini"),
+            "[environment]\ncpu = Apple M4 </script>\n\n[build]\nrustflags = -Ctarget-cpu=native\n",
+        )
+        .expect("write meta");
+        let criterion = root.join("criterion");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            50.0,
+        );
+        write_summary(&run_dir, &criterion).expect("write summary");
+
+        let index = write_report_site(&root, &root.join("site")).expect("write report");
+        let html = fs::read_to_string(index).expect("read index");
+        assert!(!html.contains("/*REPORT_DATA*/"));
+        assert!(html.contains(r#""function":"XXH3-64""#));
+        assert!(html.contains(r#""name":"build","entries":[["rustflags","-Ctarget-cpu=native"]]"#));
+        assert!(html.contains(r"Apple M4 <\/script>"));
+        assert!(!html.contains("src=\"http") && !html.contains("href=\"http"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn report_fails_without_summaries() {
+        let root = temp_dir("report-empty");
+        let run_dir = root.join("results/apple-m4-macos/runs/2026-03-01T00-00-00Z");
+        fs::create_dir_all(run_dir.join("charts")).expect("create charts dir");
+        fs::write(
+            run_dir.join("environment.ini"),
+            "[environment]\ncpu = Apple M4\n",
+        )
+        .expect("write meta");
+
+        let error = write_report_site(&root, &root.join("site")).expect_err("no records");
+        assert!(error.to_string().contains("summary.json"), "{error}");
+        assert!(!root.join("site").exists());
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
@@ -1,26 +1,44 @@
-use std::{fmt::Write as _, fs, io, path::Path};
+use std::{collections::BTreeSet, fmt::Write as _, fs, io, path::Path};
 
 use crate::{
-    environment::read_cpu_from_metadata,
+    charts::{HostSeries, write_group_charts},
+    cli::AggregateOpts,
+    criterion::BenchmarkRecord,
+    environment::{read_cpu_from_metadata, read_metadata, read_versions_from_metadata},
+    normalize::{self, RelativeTable},
+    runs::{SummaryEntry, latest_run_dir, read_summary},
     scope::{ChartSpec, Scope},
     util::{Result, workspace_root},
 };
 
-pub fn aggregate_results() -> Result<()> {
+pub fn aggregate_results(args: &AggregateOpts) -> Result<()> {
     let workspace_root = workspace_root();
     for &scope in Scope::all() {
-        write_scope_results_markdown(workspace_root, scope)?;
+        write_scope_results_markdown(workspace_root, scope, &args.references)?;
     }
     Ok(())
 }
 
-fn write_scope_results_markdown(workspace_root: &Path, scope: Scope) -> Result<()> {
+fn write_scope_results_markdown(
+    workspace_root: &Path,
+    scope: Scope,
+    references: &[String],
+) -> Result<()> {
     let path = scope.crate_results_readme(workspace_root);
     let hosts = list_hosts(workspace_root)?;
+    let mut summaries = Vec::new();
+    for host in &hosts {
+        if let Some(entries) = read_summary(&workspace_root.join("results").join(&host.dir))? {
+            summaries.push((host, entries));
+        }
+    }
 
     let mut content = String::new();
     writeln!(&mut content, "# Benchmark Results")?;
 
+    write_comparison(&mut content, workspace_root, &summaries, scope)?;
+    write_relative(&mut content, &summaries, scope, references)?;
+
     let mut has_any_chart = false;
     for chart in scope.charts() {
         let matching_hosts = list_hosts_with_chart(workspace_root, &hosts, chart)?;
@@ -37,7 +55,7 @@ fn write_scope_results_markdown(workspace_root: &Path, scope: Scope) -> Result<(
                 &mut content,
                 "### [{}]({})",
                 host.title,
-                host_readme_markdown_path(&host.id)
+                host_readme_markdown_path(&host.dir)
             )?;
             writeln!(&mut content)?;
             let alt_text = format!("{} ({})", chart.title, host.title);
@@ -45,7 +63,7 @@ fn write_scope_results_markdown(workspace_root: &Path, scope: Scope) -> Result<(
                 &mut content,
                 "![{}]({})",
                 alt_text,
-                chart_markdown_path(&host.id, chart.dest_path)
+                chart_markdown_path(&host.dir, chart.dest_path)
             )?;
         }
     }
@@ -55,38 +73,195 @@ fn write_scope_results_markdown(workspace_root: &Path, scope: Scope) -> Result<(
         writeln!(&mut content, "_No results found yet._")?;
     }
 
+    write_library_versions(&mut content, workspace_root, &hosts, scope)?;
+
     fs::write(path, content)?;
     Ok(())
 }
 
+/// Cross-host charts drawn from the run summaries of every host that has one.
+fn write_comparison(
+    content: &mut String,
+    workspace_root: &Path,
+    summaries: &[(&HostInfo, Vec<SummaryEntry>)],
+    scope: Scope,
+) -> Result<()> {
+    let charts_dir = scope.crate_charts_dir(workspace_root);
+    if charts_dir.is_dir() {
+        fs::remove_dir_all(&charts_dir)?;
+    }
+
+    let mut wrote_heading = false;
+    for &group in scope.summary_groups() {
+        let series: Vec<HostSeries<'_>> = summaries
+            .iter()
+            .map(|(host, entries)| HostSeries {
+                title: &host.title,
+                records: group_records(entries, scope, group),
+            })
+            .filter(|series| !series.records.is_empty())
+            .collect();
+        if series.is_empty() {
+            continue;
+        }
+        let title = scope.group_title(group);
+        let charts = write_group_charts(&charts_dir, group, title, &series)?;
+
+        if !wrote_heading {
+            writeln!(content)?;
+            writeln!(content, "## Cross-Platform Comparison")?;
+            wrote_heading = true;
+        }
+        writeln!(content)?;
+        writeln!(content, "### {title}")?;
+        for (heading, charts) in [
+            ("By Input Size", &charts.by_size),
+            ("By Algorithm", &charts.by_algorithm),
+        ] {
+            writeln!(content)?;
+            writeln!(content, "#### {heading}")?;
+            for chart in charts {
+                writeln!(content)?;
+                writeln!(content, "![{}](charts/{})", chart.title, chart.file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Throughput relative to a reference algorithm, per group, for hosts with run summaries.
+fn write_relative(
+    content: &mut String,
+    summaries: &[(&HostInfo, Vec<SummaryEntry>)],
+    scope: Scope,
+    references: &[String],
+) -> Result<()> {
+    let references = normalize::references(scope, references);
+    let mut wrote_heading = false;
+    for &group in scope.summary_groups() {
+        let tables: Vec<(&str, RelativeTable)> = summaries
+            .iter()
+            .filter_map(|(host, entries)| {
+                let table =
+                    RelativeTable::build(&group_records(entries, scope, group), &references)?;
+                Some((host.title.as_str(), table))
+            })
+            .collect();
+        if tables.is_empty() {
+            continue;
+        }
+        if !wrote_heading {
+            writeln!(content)?;
+            writeln!(content, "## Relative Throughput")?;
+            wrote_heading = true;
+        }
+        normalize::write_relative_results(content, scope.group_title(group), &tables)?;
+    }
+    Ok(())
+}
+
+fn group_records(entries: &[SummaryEntry], scope: Scope, group: &str) -> Vec<BenchmarkRecord> {
+    entries
+        .iter()
+        .filter(|entry| entry.scope == scope.slug() && entry.group == group)
+        .map(|entry| entry.record.clone())
+        .collect()
+}
+
+/// Crates as rows, hosts as columns; hosts that did not record versions are left out.
+fn write_library_versions(
+    content: &mut String,
+    workspace_root: &Path,
+    hosts: &[HostInfo],
+    scope: Scope,
+) -> std::fmt::Result {
+    let columns: Vec<_> = hosts
+        .iter()
+        .filter_map(|host| {
+            let versions =
+                read_versions_from_metadata(&workspace_root.join("results").join(&host.dir))?;
+            let crates = versions.for_scope(scope)?.clone();
+            Some((host, crates))
+        })
+        .collect();
+    if columns.is_empty() {
+        return Ok(());
+    }
+    let crates: BTreeSet<&String> = columns
+        .iter()
+        .flat_map(|(_, crates)| crates.keys())
+        .collect();
+
+    writeln!(content)?;
+    writeln!(content, "## Library Versions")?;
+    writeln!(content)?;
+    write!(content, "| Crate |")?;
+    for (host, _) in &columns {
+        write!(content, " {} |", host.title)?;
+    }
+    writeln!(content)?;
+    writeln!(content, "| --- |{}", " --- |".repeat(columns.len()))?;
+    for name in crates {
+        write!(content, "| `{name}` |")?;
+        for (_, versions) in &columns {
+            write!(
+                content,
+                " {} |",
+                versions.get(name).map_or("-", String::as_str)
+            )?;
+        }
+        writeln!(content)?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
-struct HostInfo {
-    id: String,
-    title: String,
+pub struct HostInfo {
+    pub id: String,
+    pub title: String,
+    /// Published run of the host, relative to `results/`.
+    pub dir: String,
 }
 
-fn list_hosts(workspace_root: &Path) -> io::Result<Vec<HostInfo>> {
+/// Hosts with a published run under `results/`, sorted by id.
+pub fn list_hosts(workspace_root: &Path) -> io::Result<Vec<HostInfo>> {
     let results_root = workspace_root.join("results");
     if !results_root.is_dir() {
         return Ok(Vec::new());
     }
 
     let mut hosts = Vec::new();
-    for entry in fs::read_dir(results_root)? {
+    for entry in fs::read_dir(&results_root)? {
         let entry = entry?;
-        let path = entry.path();
-        if !path.is_dir() {
+        let host_dir = entry.path();
+        if !host_dir.is_dir() {
             continue;
         }
+        let path = latest_run_dir(&host_dir);
         if !path.join("charts").is_dir() {
             continue;
         }
         let id = entry.file_name().to_string_lossy().into_owned();
         let title = read_host_title(&path).unwrap_or_else(|| id.clone());
-        hosts.push(HostInfo { id, title });
+        let dir = path
+            .strip_prefix(&results_root)
+            .unwrap_or(&path)
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        hosts.push(HostInfo { id, title, dir });
     }
 
     hosts.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // Machines with the same CPU and no label would otherwise share a heading.
+    let titles: Vec<String> = hosts.iter().map(|host| host.title.clone()).collect();
+    for host in &mut hosts {
+        if titles.iter().filter(|&title| title == &host.title).count() > 1 {
+            host.title = format!("{} ({})", host.title, host.id);
+        }
+    }
     Ok(hosts)
 }
 
@@ -99,7 +274,7 @@ fn list_hosts_with_chart(
     for host in hosts {
         let chart_path = workspace_root
             .join("results")
-            .join(&host.id)
+            .join(&host.dir)
             .join("charts")
             .join(chart.dest_path);
         if chart_path.is_file() {
@@ -111,7 +286,10 @@ fn list_hosts_with_chart(
 
 fn read_host_title(host_result_dir: &Path) -> Option<String> {
     if let Some(cpu) = read_cpu_from_metadata(host_result_dir) {
-        return Some(cpu);
+        return match read_metadata(host_result_dir)?.label() {
+            Some(label) => Some(format!("{cpu} ({label})")),
+            None => Some(cpu),
+        };
     }
 
     let readme = fs::read_to_string(host_result_dir.join("README.md")).ok()?;
@@ -126,12 +304,12 @@ fn read_host_title(host_result_dir: &Path) -> Option<String> {
     None
 }
 
-fn chart_markdown_path(host: &str, chart_file: &str) -> String {
-    format!("../results/{host}/charts/{chart_file}")
+fn chart_markdown_path(host_dir: &str, chart_file: &str) -> String {
+    format!("../results/{host_dir}/charts/{chart_file}")
 }
 
-fn host_readme_markdown_path(host: &str) -> String {
-    format!("../results/{host}/README.md")
+fn host_readme_markdown_path(host_dir: &str) -> String {
+    format!("../results/{host_dir}/README.md")
 }
 
 #[cfg(test)]
@@ -143,7 +321,7 @@ mod tests {
     };
 
     use super::write_scope_results_markdown;
-    use crate::scope::Scope;
+    use crate::{criterion::tests::write_case, runs::write_summary, scope::Scope};
 
     #[test]
     fn aggregate_uses_host_readme_link_and_alt_text() {
@@ -161,7 +339,7 @@ mod tests {
         fs::write(root.join("results/apple-m1/README.md"), readme).expect("write readme");
         fs::write(
             root.join("results/apple-m1/environment.ini"),
-            "[environment]\ncpu = Apple M1\n",
+            "[environment]\ncpu = Apple M1\n\n[dependencies.hash]\nblake3 = 1.8.3\n",
         )
         .expect("write meta");
         fs::write(
@@ -175,12 +353,116 @@ mod tests {
         )
         .expect("write chart");
 
-        write_scope_results_markdown(&root, Scope::Hash).expect("aggregate should succeed");
+        write_scope_results_markdown(&root, Scope::Hash, &[]).expect("aggregate should succeed");
 
         let output = fs::read_to_string(root.join("bench_hash/RESULTS.md")).expect("read output");
         assert!(output.contains("### [Apple M1](../results/apple-m1/README.md)"));
         assert!(output.contains("![Non-Cryptographic Hash Throughput (Apple M1)]"));
         assert!(output.contains("![Cryptographic Hash Throughput (Apple M1)]"));
+        assert!(output.contains("## Library Versions\n\n| Crate | Apple M1 |\n"));
+        assert!(output.contains("| `blake3` | 1.8.3 |"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn aggregate_links_latest_run() {
+        let root = temp_dir("aggregate-runs");
+        fs::create_dir_all(root.join("bench_prng")).expect("create bench_prng");
+        for run in ["2026-03-01T00-00-00Z", "2026-03-02T00-00-00Z"] {
+            let run_dir = root.join("results/apple-m4/runs").join(run);
+            fs::create_dir_all(run_dir.join("charts")).expect("create charts dir");
+            fs::write(
+                run_dir.join("environment.ini"),
+                "[environment]\ncpu = Apple M4\n",
+            )
+            .expect("write meta");
+            fs::write(
+                run_dir.join("charts/u64_generation_lines_throughput.svg"),
+                "<svg/>",
+            )
+            .expect("write chart");
+        }
+        fs::write(
+            root.join("results/apple-m4/latest"),
+            "2026-03-01T00-00-00Z\n",
+        )
+        .expect("write latest");
+
+        write_scope_results_markdown(&root, Scope::Prng, &[]).expect("aggregate should succeed");
+
+        let output = fs::read_to_string(root.join("bench_prng/RESULTS.md")).expect("read output");
+        assert!(
+            output.contains(
+                "### [Apple M4](../results/apple-m4/runs/2026-03-01T00-00-00Z/README.md)"
+            )
+        );
+        assert!(!output.contains("2026-03-02T00-00-00Z"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn aggregate_draws_cross_host_charts() {
+        let root = temp_dir("aggregate-compare");
+        fs::create_dir_all(root.join("bench_hash/charts")).expect("create bench_hash");
+        fs::write(root.join("bench_hash/charts/stale.svg"), "<svg/>").expect("write stale");
+        for (host, cpu, scale) in [
+            ("apple-m4-macos", "Apple M4", 1.0),
+            ("ryzen-linux", "Ryzen", 0.5),
+        ] {
+            let run_dir = root
+                .join("results")
+                .join(host)
+                .join("runs/2026-03-01T00-00-00Z");
+            fs::create_dir_all(run_dir.join("charts")).expect("create charts dir");
+            fs::write(
+                run_dir.join("environment.ini"),
+                format!("[environment]\ncpu = {cpu}\n"),
+            )
+            .expect("write meta");
+            let criterion = root.join("criterion").join(host);
+            for size in [64, 1024] {
+                for (function, mean_ns) in [("XXH3-64", 10.0), ("FxHash", 20.0)] {
+                    write_case(
+                        &criterion,
+                        "non_cryptographic_hash",
+                        function,
+                        &size.to_string(),
+                        size,
+                        mean_ns * scale * size as f64 / 64.0,
+                    );
+                }
+            }
+            write_summary(&run_dir, &criterion).expect("write summary");
+        }
+
+        write_scope_results_markdown(&root, Scope::Hash, &[]).expect("aggregate should succeed");
+
+        let output = fs::read_to_string(root.join("bench_hash/RESULTS.md")).expect("read output");
+        assert!(
+            output.contains(
+                "## Cross-Platform Comparison\n\n### Non-Cryptographic Hash Throughput\n"
+            )
+        );
+        assert!(output.contains(
+            "![Non-Cryptographic Hash Throughput, 1 KiB](charts/non_cryptographic_hash-1024.svg)"
+        ));
+        assert!(output.contains(
+            "![Non-Cryptographic Hash Throughput, XXH3-64](charts/non_cryptographic_hash-xxh3-64.svg)"
+        ));
+        assert!(!output.contains("### Cryptographic Hash Throughput"));
+        let relative = &output[output
+            .find("## Relative Throughput")
+            .expect("relative section")..];
+        assert!(relative.contains("| Algorithm | Apple M4 | Ryzen |\n"));
+        assert!(relative.contains("| `FxHash` | 🟨 0.50× | 🟨 0.50× |\n"));
+        let charts = root.join("bench_hash/charts");
+        assert!(!charts.join("stale.svg").exists());
+        let svg =
+            fs::read_to_string(charts.join("non_cryptographic_hash-64.svg")).expect("read svg");
+        assert!(svg.contains("<svg") && svg.contains("Apple M4") && svg.contains("Ryzen"));
+        assert!(charts.join("non_cryptographic_hash-fxhash.svg").is_file());
 
         fs::remove_dir_all(root).expect("cleanup");
     }
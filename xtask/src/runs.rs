@@ -0,0 +1,341 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::{
+    criterion::{BenchmarkRecord, read_group},
+    scope::Scope,
+    util::Result,
+};
+
+/// Subdirectory of `results/{host}` holding one directory per collected run.
+pub const RUNS_DIR: &str = "runs";
+/// File in `results/{host}` naming the run `aggregate` publishes.
+pub const LATEST_FILE: &str = "latest";
+/// Throughput summary of a run, read back by `xtask history`.
+pub const SUMMARY_FILE: &str = "summary.json";
+
+/// One benchmark case of a run summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryEntry {
+    pub scope: String,
+    pub group: String,
+    #[serde(flatten)]
+    pub record: BenchmarkRecord,
+}
+
+/// Id of a run collected now: a UTC timestamp that sorts chronologically.
+pub fn new_run_id() -> String {
+    format_run_id(OffsetDateTime::now_utc())
+}
+
+fn format_run_id(at: OffsetDateTime) -> String {
+    let at = at.to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}-{:02}-{:02}Z",
+        at.year(),
+        u8::from(at.month()),
+        at.day(),
+        at.hour(),
+        at.minute(),
+        at.second()
+    )
+}
+
+/// `2026-03-01T08:44:54Z` for the run id `2026-03-01T08-44-54Z`; `None` for other names.
+pub fn run_timestamp(run_id: &str) -> Option<String> {
+    let (date, clock) = run_id.split_once('T')?;
+    let clock = clock.strip_suffix('Z')?;
+    if date.len() != 10 || clock.len() != 8 {
+        return None;
+    }
+    Some(format!("{date}T{}Z", clock.replace('-', ":")))
+}
+
+/// Completed runs of a host, oldest first.
+pub fn list_runs(host_dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let runs_dir = host_dir.join(RUNS_DIR);
+    if !runs_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut runs = Vec::new();
+    for entry in fs::read_dir(runs_dir)? {
+        let entry = entry?;
+        let id = entry.file_name().to_string_lossy().into_owned();
+        // Skip `.{id}.tmp` staging directories of interrupted collects.
+        if entry.path().is_dir() && !id.starts_with('.') {
+            runs.push((id, entry.path()));
+        }
+    }
+    runs.sort();
+    Ok(runs)
+}
+
+/// Directory of the run `latest` points to.
+///
+/// Falls back to the newest run, then to `host_dir` itself for hosts collected before
+/// runs were kept.
+pub fn latest_run_dir(host_dir: &Path) -> PathBuf {
+    if let Ok(id) = fs::read_to_string(host_dir.join(LATEST_FILE)) {
+        let dir = host_dir.join(RUNS_DIR).join(id.trim());
+        if dir.is_dir() {
+            return dir;
+        }
+    }
+    match list_runs(host_dir) {
+        Ok(mut runs) if !runs.is_empty() => runs.pop().expect("non-empty").1,
+        _ => host_dir.to_path_buf(),
+    }
+}
+
+pub fn set_latest(host_dir: &Path, run_id: &str) -> Result<()> {
+    fs::write(host_dir.join(LATEST_FILE), format!("{run_id}\n"))?;
+    Ok(())
+}
+
+/// Move results collected before runs were kept into a run of their own, named after
+/// the `Running at` line of their README.
+pub fn migrate_legacy_layout(host_dir: &Path) -> Result<()> {
+    if !host_dir.is_dir() {
+        return Ok(());
+    }
+    let mut legacy = Vec::new();
+    for entry in fs::read_dir(host_dir)? {
+        let name = entry?.file_name();
+        if name != RUNS_DIR && name != LATEST_FILE {
+            legacy.push(name);
+        }
+    }
+    if legacy.is_empty() {
+        return Ok(());
+    }
+
+    let readme = host_dir.join("README.md");
+    let run_id = fs::read_to_string(&readme)
+        .ok()
+        .and_then(|readme| readme_run_id(&readme))
+        .or_else(|| {
+            let modified = fs::metadata(&readme).ok()?.modified().ok()?;
+            Some(format_run_id(modified.into()))
+        })
+        .unwrap_or_else(new_run_id);
+    let run_dir = host_dir.join(RUNS_DIR).join(&run_id);
+    if run_dir.exists() {
+        return Err(format!(
+            "cannot migrate {}: {} exists",
+            host_dir.display(),
+            run_dir.display()
+        )
+        .into());
+    }
+    fs::create_dir_all(&run_dir)?;
+    for name in legacy {
+        fs::rename(host_dir.join(&name), run_dir.join(&name))?;
+    }
+    set_latest(host_dir, &run_id)
+}
+
+/// Run id from a README line such as `Running at 2026-02-28 16:44:54 +0800.`.
+pub fn readme_run_id(readme: &str) -> Option<String> {
+    let line = readme
+        .lines()
+        .find_map(|line| line.strip_prefix("Running at "))?
+        .trim_end_matches('.');
+    let mut parts = line.split_whitespace();
+    let (date, clock, offset) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let numbers = |value: &str, separator: char| -> Option<Vec<i32>> {
+        value
+            .split(separator)
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    let (&[year, month, day], &[hour, minute, second]) = (
+        numbers(date, '-')?.as_slice(),
+        numbers(clock, ':')?.as_slice(),
+    ) else {
+        return None;
+    };
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let offset = offset.trim_start_matches(['+', '-']);
+    let (offset_hours, offset_minutes) = (
+        offset.get(..2)?.parse::<i8>().ok()?,
+        offset.get(2..)?.parse::<i8>().ok()?,
+    );
+
+    let date =
+        Date::from_calendar_date(year, Month::try_from(month as u8).ok()?, day as u8).ok()?;
+    let time = Time::from_hms(hour as u8, minute as u8, second as u8).ok()?;
+    let offset = UtcOffset::from_hms(sign * offset_hours, sign * offset_minutes, 0).ok()?;
+    Some(format_run_id(
+        PrimitiveDateTime::new(date, time).assume_offset(offset),
+    ))
+}
+
+/// Write the wall-time records of every scope's summary groups to `run_dir`.
+pub fn write_summary(run_dir: &Path, criterion_dir: &Path) -> Result<()> {
+    let mut entries = Vec::new();
+    for &scope in Scope::all() {
+        for &group in scope.summary_groups() {
+            for record in read_group(criterion_dir, group)? {
+                entries.push(SummaryEntry {
+                    scope: scope.slug().to_owned(),
+                    group: group.to_owned(),
+                    record,
+                });
+            }
+        }
+    }
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|error| format!("failed to encode run summary: {error}"))?;
+    fs::write(run_dir.join(SUMMARY_FILE), json)?;
+    Ok(())
+}
+
+/// Summary of a run; `None` for runs collected before summaries were written.
+pub fn read_summary(run_dir: &Path) -> Result<Option<Vec<SummaryEntry>>> {
+    let path = run_dir.join(SUMMARY_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path)?;
+    let entries = serde_json::from_str(&raw)
+        .map_err(|error| format!("failed to parse {}: {error}", path.display()))?;
+    Ok(Some(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{
+        LATEST_FILE, latest_run_dir, list_runs, migrate_legacy_layout, read_summary, readme_run_id,
+        run_timestamp, set_latest, write_summary,
+    };
+    use crate::criterion::tests::write_case;
+
+    #[test]
+    fn readme_run_id_converts_to_utc() {
+        let readme = "# Benchmark Results\n\nRunning at 2026-02-28 16:44:54 +0800.\n";
+        assert_eq!(
+            readme_run_id(readme).as_deref(),
+            Some("2026-02-28T08-44-54Z")
+        );
+        let readme = "Running at 2026-02-28 22:30:00 -0530.\n";
+        assert_eq!(
+            readme_run_id(readme).as_deref(),
+            Some("2026-03-01T04-00-00Z")
+        );
+        assert_eq!(readme_run_id("Running at unknown.\n"), None);
+    }
+
+    #[test]
+    fn run_timestamp_is_rfc3339() {
+        assert_eq!(
+            run_timestamp("2026-03-01T08-44-54Z").as_deref(),
+            Some("2026-03-01T08:44:54Z")
+        );
+        assert_eq!(run_timestamp(""), None);
+        assert_eq!(run_timestamp("colleague-m1"), None);
+    }
+
+    #[test]
+    fn migrate_moves_flat_layout_into_run() {
+        let host = temp_dir("runs-migrate");
+        fs::create_dir_all(host.join("charts")).expect("create charts");
+        fs::write(host.join("charts/a.svg"), "<svg/>").expect("write chart");
+        fs::write(
+            host.join("README.md"),
+            "Running at 2026-02-28 16:44:54 +0800.\n",
+        )
+        .expect("write readme");
+        assert_eq!(latest_run_dir(&host), host);
+
+        migrate_legacy_layout(&host).expect("migrate");
+        let run = host.join("runs/2026-02-28T08-44-54Z");
+        assert!(run.join("charts/a.svg").is_file());
+        assert!(!host.join("README.md").exists());
+        assert_eq!(latest_run_dir(&host), run);
+
+        // Already migrated: nothing to do.
+        migrate_legacy_layout(&host).expect("migrate again");
+        assert_eq!(list_runs(&host).expect("list runs").len(), 1);
+
+        fs::remove_dir_all(host).expect("cleanup");
+    }
+
+    #[test]
+    fn latest_pointer_and_run_order() {
+        let host = temp_dir("runs-latest");
+        for id in [
+            "2026-03-02T00-00-00Z",
+            "2026-03-01T00-00-00Z",
+            ".2026-03-03T00-00-00Z.tmp",
+        ] {
+            fs::create_dir_all(host.join("runs").join(id)).expect("create run");
+        }
+        let runs: Vec<String> = list_runs(&host)
+            .expect("list runs")
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(runs, ["2026-03-01T00-00-00Z", "2026-03-02T00-00-00Z"]);
+        assert_eq!(
+            latest_run_dir(&host),
+            host.join("runs/2026-03-02T00-00-00Z")
+        );
+
+        set_latest(&host, "2026-03-01T00-00-00Z").expect("set latest");
+        assert!(host.join(LATEST_FILE).is_file());
+        assert_eq!(
+            latest_run_dir(&host),
+            host.join("runs/2026-03-01T00-00-00Z")
+        );
+
+        fs::remove_dir_all(host).expect("cleanup");
+    }
+
+    #[test]
+    fn summary_roundtrip() {
+        let root = temp_dir("runs-summary");
+        let criterion = root.join("criterion");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            50.0,
+        );
+        write_case(&criterion, "unaligned_hash", "XXH3-64", "0", 4096, 100.0);
+
+        assert!(read_summary(&root).expect("read missing").is_none());
+        write_summary(&root, &criterion).expect("write summary");
+        let entries = read_summary(&root).expect("read").expect("summary exists");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].scope, "hash");
+        assert_eq!(entries[0].record.function, "XXH3-64");
+        assert_eq!(
+            entries[0].record.throughput_per_sec(),
+            Some(1024.0 * 1e9 / 50.0)
+        );
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
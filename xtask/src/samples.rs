@@ -0,0 +1,102 @@
+use std::{fs, path::Path};
+
+use crate::{
+    criterion::find_case_dirs,
+    import::extract_archive,
+    scope::Scope,
+    util::{Result, run_capture_checked},
+};
+
+/// Raw samples of a run's summary groups, kept next to its `summary.json`.
+pub const SAMPLES_ARCHIVE: &str = "samples.tar.zst";
+/// Files of a criterion case directory kept as raw samples.
+const SAMPLE_FILES: &[&str] = &["benchmark.json", "estimates.json", "sample.json"];
+
+/// Copy the case directories of `groups` from `criterion_dir` to `samples_dir`, keeping
+/// only [`SAMPLE_FILES`]; returns the number of cases with a `sample.json`.
+pub fn copy_samples(criterion_dir: &Path, samples_dir: &Path, groups: &[&str]) -> Result<usize> {
+    let mut sampled = 0;
+    for group in groups {
+        let group_dir = criterion_dir.join(group);
+        if !group_dir.is_dir() {
+            continue;
+        }
+        let mut case_dirs = Vec::new();
+        find_case_dirs(&group_dir, &mut case_dirs)?;
+        for case_dir in case_dirs {
+            let target = samples_dir.join(case_dir.strip_prefix(criterion_dir)?);
+            fs::create_dir_all(&target)?;
+            for file in SAMPLE_FILES {
+                if case_dir.join(file).is_file() {
+                    fs::copy(case_dir.join(file), target.join(file))?;
+                }
+            }
+            sampled += usize::from(case_dir.join("sample.json").is_file());
+        }
+    }
+    Ok(sampled)
+}
+
+/// Compress the samples of every scope's summary groups into `run_dir`'s
+/// [`SAMPLES_ARCHIVE`]; returns `false`, writing nothing, when there are none.
+pub fn archive_samples(criterion_dir: &Path, run_dir: &Path) -> Result<bool> {
+    let groups: Vec<&str> = Scope::all()
+        .iter()
+        .flat_map(|scope| scope.summary_groups())
+        .copied()
+        .collect();
+    let staging_dir = run_dir.join(".samples");
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    let archive = run_dir.join(SAMPLES_ARCHIVE);
+    let result = copy_samples(criterion_dir, &staging_dir, &groups).and_then(|sampled| {
+        if sampled > 0 {
+            run_capture_checked("tar", &[
+                "--zstd",
+                "-cf",
+                &archive.to_string_lossy(),
+                "-C",
+                &staging_dir.to_string_lossy(),
+                ".",
+            ])
+            .map_err(|error| format!("cannot write {}: {error}", archive.display()))?;
+        }
+        Ok(sampled > 0)
+    });
+    // Neither the staging copy nor a partial archive may end up in the run.
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    if result.is_err() && archive.exists() {
+        fs::remove_file(&archive)?;
+    }
+    result
+}
+
+/// [`archive_samples`] for a run being added to `results/`: raw samples are optional, so
+/// a run without them, or a `tar` that cannot write zstd, only warns.
+pub fn keep_samples(criterion_dir: &Path, run_dir: &Path) {
+    match archive_samples(criterion_dir, run_dir) {
+        Ok(true) => {}
+        Ok(false) => eprintln!(
+            "warning: {} holds no raw samples; `xtask reanalyze` cannot revisit this run",
+            criterion_dir.display()
+        ),
+        Err(error) => eprintln!(
+            "warning: cannot keep raw samples ({error}); `xtask reanalyze` cannot revisit \
+             this run"
+        ),
+    }
+}
+
+/// Unpack the [`SAMPLES_ARCHIVE`] of `run_dir` into `extract_dir` as a criterion
+/// directory; returns `false` for runs collected without samples.
+pub fn extract_samples(run_dir: &Path, extract_dir: &Path) -> Result<bool> {
+    let archive = run_dir.join(SAMPLES_ARCHIVE);
+    if !archive.is_file() {
+        return Ok(false);
+    }
+    extract_archive(&archive, extract_dir)?;
+    Ok(true)
+}
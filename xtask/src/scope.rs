@@ -3,6 +3,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::criterion::CRITERION_DIR;
+
 #[derive(Debug, Clone, Copy)]
 pub struct ChartSpec {
     pub title: &'static str,
@@ -10,6 +12,19 @@ pub struct ChartSpec {
     pub dest_path: &'static str,
 }
 
+impl ChartSpec {
+    /// The chart inside `criterion_dir`, which stands in for the `target/criterion` of
+    /// `src_path`.
+    pub fn source(&self, criterion_dir: &Path) -> PathBuf {
+        let relative = self
+            .src_path
+            .strip_prefix(CRITERION_DIR)
+            .unwrap_or(self.src_path)
+            .trim_start_matches('/');
+        criterion_dir.join(relative)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Scope {
     Hash,
@@ -42,6 +57,23 @@ impl Scope {
         }
     }
 
+    /// Main criterion groups, summarized in the per-measurement tables of the host README.
+    pub fn summary_groups(self) -> &'static [&'static str] {
+        match self {
+            Scope::Hash => &["non_cryptographic_hash", "cryptographic_hash"],
+            Scope::Prng => &["u64_generation", "bytes_generation"],
+        }
+    }
+
+    /// Algorithms relative-throughput tables are normalized to when none is requested,
+    /// tried in order per group.
+    pub fn default_references(self) -> &'static [&'static str] {
+        match self {
+            Scope::Hash => &["XXH3-64", "BLAKE3-256"],
+            Scope::Prng => &["PCG64"],
+        }
+    }
+
     pub fn charts(self) -> &'static [ChartSpec] {
         match self {
             Scope::Hash => &[
@@ -55,6 +87,56 @@ impl Scope {
                     src_path: "target/criterion/cryptographic_hash/report/lines_throughput.svg",
                     dest_path: "cryptographic_hash_lines_throughput.svg",
                 },
+                ChartSpec {
+                    title: "Non-Cryptographic Hash Throughput (Zeros)",
+                    src_path: "target/criterion/non_cryptographic_hash_zeros/report/lines_throughput.svg",
+                    dest_path: "non_cryptographic_hash_zeros_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Non-Cryptographic Hash Throughput (ASCII Text)",
+                    src_path: "target/criterion/non_cryptographic_hash_ascii/report/lines_throughput.svg",
+                    dest_path: "non_cryptographic_hash_ascii_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Non-Cryptographic Hash Throughput (Repeated Pattern)",
+                    src_path: "target/criterion/non_cryptographic_hash_repeated/report/lines_throughput.svg",
+                    dest_path: "non_cryptographic_hash_repeated_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Non-Cryptographic Hash Throughput (Structured Records)",
+                    src_path: "target/criterion/non_cryptographic_hash_records/report/lines_throughput.svg",
+                    dest_path: "non_cryptographic_hash_records_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Cryptographic Hash Throughput (Zeros)",
+                    src_path: "target/criterion/cryptographic_hash_zeros/report/lines_throughput.svg",
+                    dest_path: "cryptographic_hash_zeros_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Cryptographic Hash Throughput (ASCII Text)",
+                    src_path: "target/criterion/cryptographic_hash_ascii/report/lines_throughput.svg",
+                    dest_path: "cryptographic_hash_ascii_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Cryptographic Hash Throughput (Repeated Pattern)",
+                    src_path: "target/criterion/cryptographic_hash_repeated/report/lines_throughput.svg",
+                    dest_path: "cryptographic_hash_repeated_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Cryptographic Hash Throughput (Structured Records)",
+                    src_path: "target/criterion/cryptographic_hash_records/report/lines_throughput.svg",
+                    dest_path: "cryptographic_hash_records_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Cold-Cache Hash Throughput",
+                    src_path: "target/criterion/cold_cache_hash/report/lines_throughput.svg",
+                    dest_path: "cold_cache_hash_lines_throughput.svg",
+                },
+                ChartSpec {
+                    title: "Batch Small-Key Hash Throughput",
+                    src_path: "target/criterion/batch_small_keys/report/lines_throughput.svg",
+                    dest_path: "batch_small_keys_lines_throughput.svg",
+                },
             ],
             Scope::Prng => &[
                 ChartSpec {
@@ -74,6 +156,20 @@ impl Scope {
     pub fn crate_results_readme(self, workspace_root: &Path) -> PathBuf {
         workspace_root.join(self.bench_crate()).join("RESULTS.md")
     }
+
+    /// Cross-host comparison charts generated by `aggregate`, next to `RESULTS.md`.
+    pub fn crate_charts_dir(self, workspace_root: &Path) -> PathBuf {
+        workspace_root.join(self.bench_crate()).join("charts")
+    }
+
+    /// Chart title of a criterion group, e.g. `Cryptographic Hash Throughput`.
+    pub fn group_title(self, group: &str) -> &str {
+        let dest_path = format!("{group}_lines_throughput.svg");
+        self.charts()
+            .iter()
+            .find(|chart| chart.dest_path == dest_path)
+            .map_or(group, |chart| chart.title)
+    }
 }
 
 impl fmt::Display for Scope {
@@ -0,0 +1,423 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    bench,
+    build_config::BuildConfig,
+    cli::RunOpts,
+    criterion::{BenchmarkRecord, output_stamp, read_group},
+    environment::CompilerSpec,
+    metadata::Ini,
+    placement::Placement,
+    runs,
+    scope::Scope,
+    util::{Result, load_last, save_last},
+};
+
+/// Cargo target directories of `run --toolchain`, one per toolchain, each with its own
+/// `criterion/` so toolchains never overwrite each other's (or the default) results.
+pub const TOOLCHAINS_DIR: &str = "target/toolchains";
+/// Toolchain comparison tables, inside [`TOOLCHAINS_DIR`] and a collected run's
+/// [`RUN_TOOLCHAINS_DIR`].
+pub const COMPARISON_FILE: &str = "comparison.md";
+/// Toolchain, versions and build configuration of the output in a toolchain's target
+/// directory, and of its collected copy.
+const TOOLCHAIN_FILE: &str = "toolchain.ini";
+/// Per-toolchain results of a collected run, one `{name}/` directory each.
+const RUN_TOOLCHAINS_DIR: &str = "toolchains";
+
+/// A rustup toolchain benchmarks are built with.
+#[derive(Debug, Clone)]
+pub struct Toolchain {
+    /// As given to `cargo +{name}`, e.g. `stable`, `nightly` or `1.89`.
+    pub name: String,
+    pub compiler: CompilerSpec,
+    /// Detected with the toolchain's own `rustc`, whose target features may differ.
+    pub build: BuildConfig,
+}
+
+impl Toolchain {
+    pub fn detect(name: &str, workspace_root: &Path) -> Result<Self> {
+        if name.is_empty()
+            || name.starts_with('+')
+            || name.contains(|ch: char| ch == '/' || ch == '\\' || ch.is_whitespace())
+        {
+            return Err(format!("invalid toolchain `{name}`").into());
+        }
+        let compiler = CompilerSpec::detect_toolchain(name).ok_or_else(|| {
+            format!("toolchain `{name}` is not installed; run `rustup toolchain install {name}`")
+        })?;
+        Ok(Self {
+            name: name.to_owned(),
+            compiler,
+            build: BuildConfig::detect(workspace_root, Some(name)),
+        })
+    }
+
+    /// `CARGO_TARGET_DIR` of the toolchain's builds and criterion output.
+    pub fn target_dir(&self, workspace_root: &Path) -> PathBuf {
+        workspace_root.join(TOOLCHAINS_DIR).join(&self.name)
+    }
+
+    fn encode_ini(&self) -> String {
+        format!(
+            "[toolchain]\nname = {}\nrustc = {}\nllvm = {}\n\n{}",
+            self.name,
+            self.compiler.rustc,
+            self.compiler.llvm,
+            self.build.encode_ini()
+        )
+    }
+
+    fn decode_ini(input: &str) -> std::result::Result<Self, String> {
+        let ini = Ini::parse(input)?;
+        let field = |key: &str| {
+            ini.get("toolchain", key)
+                .map(str::to_owned)
+                .ok_or_else(|| format!("missing key `{key}` in [toolchain]"))
+        };
+        Ok(Self {
+            name: field("name")?,
+            compiler: CompilerSpec {
+                rustc: field("rustc")?,
+                llvm: field("llvm")?,
+            },
+            build: BuildConfig::decode_section(ini.entries("build"))?,
+        })
+    }
+
+    /// Record what built the criterion output `stamp` of this toolchain, for `collect`.
+    fn save_last(&self, workspace_root: &Path, stamp: &str) -> Result<()> {
+        save_last(
+            workspace_root,
+            &format!("{TOOLCHAINS_DIR}/{}/{TOOLCHAIN_FILE}", self.name),
+            stamp,
+            &self.encode_ini(),
+        )
+    }
+
+    /// The toolchain whose `xtask run --toolchain` wrote the output in `target_dir`, if any.
+    fn load_last(workspace_root: &Path, target_dir: &Path) -> Option<Self> {
+        let name = target_dir.file_name()?.to_str()?;
+        let stamp = output_stamp(&target_dir.join("criterion"));
+        let raw = load_last(
+            workspace_root,
+            &format!("{TOOLCHAINS_DIR}/{name}/{TOOLCHAIN_FILE}"),
+            stamp.as_deref(),
+        )?;
+        Self::decode_ini(&raw)
+            .ok()
+            .filter(|toolchain| toolchain.name == name)
+    }
+}
+
+/// Run the scopes of `args` once per toolchain, then write and print the comparison.
+pub fn run_toolchains(args: &RunOpts, placement: &Placement, workspace_root: &Path) -> Result<()> {
+    let mut toolchains: Vec<Toolchain> = Vec::new();
+    for name in &args.toolchain {
+        if !toolchains.iter().any(|toolchain| &toolchain.name == name) {
+            toolchains.push(Toolchain::detect(name, workspace_root)?);
+        }
+    }
+    for toolchain in &toolchains {
+        eprintln!(
+            "Benchmarking with {} ({}, LLVM {})",
+            toolchain.name, toolchain.compiler.rustc, toolchain.compiler.llvm
+        );
+        let criterion_dir = toolchain.target_dir(workspace_root).join("criterion");
+        let before = output_stamp(&criterion_dir);
+        for &scope in args.scope.to_scopes() {
+            bench::run_benchmark(
+                scope,
+                &args.bench_args,
+                placement,
+                workspace_root,
+                Some(toolchain),
+            )?;
+        }
+        if let Some(stamp) =
+            output_stamp(&criterion_dir).filter(|stamp| Some(stamp) != before.as_ref())
+        {
+            toolchain.save_last(workspace_root, &stamp)?;
+        }
+    }
+
+    let mut comparison = Vec::new();
+    let compared = write_comparison(
+        &mut comparison,
+        &toolchains,
+        args.scope.to_scopes(),
+        workspace_root,
+    )?;
+    if compared == 0 {
+        eprintln!("note: no wall-time results to compare; run without --measurement");
+        return Ok(());
+    }
+    let path = workspace_root.join(TOOLCHAINS_DIR).join(COMPARISON_FILE);
+    fs::write(&path, &comparison)?;
+    std::io::stdout().write_all(&comparison)?;
+    eprintln!("wrote {}", path.display());
+    Ok(())
+}
+
+/// Copy the wall-time results of every toolchain run into `run_dir/toolchains/{name}/`,
+/// with the toolchain and build configuration that produced them, and compare them.
+///
+/// Target directories whose output was not written by `xtask run --toolchain` are skipped.
+pub fn collect_toolchains(workspace_root: &Path, run_dir: &Path) -> Result<()> {
+    let Ok(entries) = fs::read_dir(workspace_root.join(TOOLCHAINS_DIR)) else {
+        return Ok(());
+    };
+    let mut target_dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    target_dirs.sort();
+
+    let mut toolchains = Vec::new();
+    for target_dir in target_dirs {
+        let Some(toolchain) = Toolchain::load_last(workspace_root, &target_dir) else {
+            eprintln!(
+                "note: skipped {}; its results were not written by `xtask run --toolchain`",
+                target_dir.display()
+            );
+            continue;
+        };
+        let toolchain_dir = run_dir.join(RUN_TOOLCHAINS_DIR).join(&toolchain.name);
+        fs::create_dir_all(&toolchain_dir)?;
+        fs::write(toolchain_dir.join(TOOLCHAIN_FILE), toolchain.encode_ini())?;
+        runs::write_summary(&toolchain_dir, &target_dir.join("criterion"))?;
+        toolchains.push(toolchain);
+    }
+    if toolchains.is_empty() {
+        return Ok(());
+    }
+
+    let mut comparison = Vec::new();
+    write_comparison(&mut comparison, &toolchains, Scope::all(), workspace_root)?;
+    fs::write(
+        run_dir.join(RUN_TOOLCHAINS_DIR).join(COMPARISON_FILE),
+        comparison,
+    )?;
+    Ok(())
+}
+
+/// One markdown table per algorithm of each summary group: parameters as rows, toolchains
+/// as columns, each cell the throughput and its change against the first toolchain with one.
+///
+/// Returns the number of tables written.
+fn write_comparison(
+    writer: &mut impl Write,
+    toolchains: &[Toolchain],
+    scopes: &[Scope],
+    workspace_root: &Path,
+) -> Result<usize> {
+    writeln!(writer, "# Toolchain Comparison")?;
+    writeln!(writer)?;
+    writeln!(writer, "| Toolchain | rustc | LLVM |")?;
+    writeln!(writer, "| --- | --- | --- |")?;
+    for toolchain in toolchains {
+        writeln!(
+            writer,
+            "| `{}` | {} | {} |",
+            toolchain.name, toolchain.compiler.rustc, toolchain.compiler.llvm
+        )?;
+    }
+
+    let mut tables = 0;
+    for &scope in scopes {
+        for &group in scope.summary_groups() {
+            let mut results: Vec<Vec<BenchmarkRecord>> = Vec::new();
+            for toolchain in toolchains {
+                let criterion_dir = toolchain.target_dir(workspace_root).join("criterion");
+                results.push(read_group(&criterion_dir, group)?);
+            }
+            let mut functions: Vec<&str> = Vec::new();
+            for record in results.iter().flatten() {
+                if !functions.contains(&record.function.as_str()) {
+                    functions.push(&record.function);
+                }
+            }
+
+            for function in functions {
+                let mut parameters: Vec<String> = Vec::new();
+                for record in results.iter().flatten() {
+                    let parameter = record.display_parameter();
+                    if record.function == function && !parameters.contains(&parameter) {
+                        parameters.push(parameter);
+                    }
+                }
+
+                writeln!(writer)?;
+                writeln!(writer, "## {} / {function}", scope.group_title(group))?;
+                writeln!(writer)?;
+                write!(writer, "| Parameter |")?;
+                for toolchain in toolchains {
+                    write!(writer, " `{}` |", toolchain.name)?;
+                }
+                writeln!(writer)?;
+                writeln!(writer, "| --- |{}", " --- |".repeat(toolchains.len()))?;
+                for parameter in &parameters {
+                    write!(writer, "| {parameter} |")?;
+                    let mut baseline = None;
+                    for records in &results {
+                        let record = records.iter().find(|record| {
+                            record.function == function && &record.display_parameter() == parameter
+                        });
+                        let cell = match record.and_then(|record| {
+                            Some((record.throughput_per_sec()?, record.display_throughput()?))
+                        }) {
+                            Some((rate, display)) => match baseline {
+                                Some(before) => {
+                                    format!("{display} ({:+.1}%)", (rate / before - 1.0) * 100.0)
+                                }
+                                None => {
+                                    baseline = Some(rate);
+                                    display
+                                }
+                            },
+                            None => "-".to_owned(),
+                        };
+                        write!(writer, " {cell} |")?;
+                    }
+                    writeln!(writer)?;
+                }
+                tables += 1;
+            }
+        }
+    }
+    Ok(tables)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{Toolchain, collect_toolchains, write_comparison};
+    use crate::{
+        build_config::BuildConfig,
+        criterion::{output_stamp, tests::write_case},
+        environment::CompilerSpec,
+        runs::read_summary,
+        scope::Scope,
+    };
+
+    #[test]
+    fn comparison_table_per_algorithm() {
+        let root = temp_dir("toolchains");
+        let toolchains: Vec<Toolchain> = [("stable", "19.1.7"), ("nightly", "21.1.8")]
+            .into_iter()
+            .map(|(name, llvm)| Toolchain {
+                name: name.to_owned(),
+                compiler: CompilerSpec {
+                    rustc: format!("rustc 1.93.0-{name}"),
+                    llvm: llvm.to_owned(),
+                },
+                build: BuildConfig::default(),
+            })
+            .collect();
+        let group = "non_cryptographic_hash";
+        let stable = toolchains[0].target_dir(&root).join("criterion");
+        let nightly = toolchains[1].target_dir(&root).join("criterion");
+        write_case(&stable, group, "XXH3-64", "1024", 1024, 100.0);
+        write_case(&stable, group, "XXH3-64", "64", 64, 10.0);
+        write_case(&nightly, group, "XXH3-64", "1024", 1024, 80.0);
+        write_case(&nightly, group, "FxHash", "1024", 1024, 50.0);
+
+        let mut out = Vec::new();
+        let tables =
+            write_comparison(&mut out, &toolchains, &[Scope::Hash], &root).expect("compare");
+        let out = String::from_utf8(out).expect("utf-8");
+        assert_eq!(tables, 2);
+        assert!(out.contains("| `nightly` | rustc 1.93.0-nightly | 21.1.8 |"));
+        assert!(out.contains("| Parameter | `stable` | `nightly` |\n| --- | --- | --- |\n"));
+        assert!(
+            out.contains("| 1 KiB | 9.54 GiB/s | 11.92 GiB/s (+25.0%) |"),
+            "{out}"
+        );
+        assert!(out.contains("| 64 B | 5.96 GiB/s | - |"), "{out}");
+        assert!(out.contains("/ FxHash\n"), "{out}");
+        assert!(out.contains("| 1 KiB | - | 19.07 GiB/s |"), "{out}");
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn collect_copies_results_of_toolchain_runs() {
+        let root = temp_dir("toolchains-collect");
+        let toolchain = Toolchain {
+            name: "nightly".to_owned(),
+            compiler: CompilerSpec {
+                rustc: "rustc 1.95.0-nightly".to_owned(),
+                llvm: "21.1.8".to_owned(),
+            },
+            build: BuildConfig {
+                rustflags: vec!["-Ctarget-cpu=native".to_owned()],
+                target_features: vec!["avx2".to_owned()],
+                ..BuildConfig::default()
+            },
+        };
+        let criterion = toolchain.target_dir(&root).join("criterion");
+        write_case(
+            &criterion,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            80.0,
+        );
+        let stamp = output_stamp(&criterion).expect("stamp");
+        toolchain.save_last(&root, &stamp).expect("save");
+        // Output of a run outside `xtask run --toolchain`.
+        let unstamped = root.join("target/toolchains/beta/criterion");
+        write_case(
+            &unstamped,
+            "non_cryptographic_hash",
+            "XXH3-64",
+            "1024",
+            1024,
+            90.0,
+        );
+
+        let run_dir = root.join("run");
+        collect_toolchains(&root, &run_dir).expect("collect");
+        let collected = run_dir.join("toolchains/nightly");
+        let summary = read_summary(&collected).expect("read").expect("summary");
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].record.function, "XXH3-64");
+        let recorded = Toolchain::decode_ini(
+            &fs::read_to_string(collected.join("toolchain.ini")).expect("toolchain.ini"),
+        )
+        .expect("decode");
+        assert_eq!(recorded.compiler.rustc, toolchain.compiler.rustc);
+        assert_eq!(recorded.build, toolchain.build);
+        assert!(!run_dir.join("toolchains/beta").exists());
+        let comparison =
+            fs::read_to_string(run_dir.join("toolchains/comparison.md")).expect("comparison");
+        assert!(comparison.contains("| `nightly` | rustc 1.95.0-nightly | 21.1.8 |"));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn rejects_invalid_toolchain_names() {
+        for name in ["", "+nightly", "../stable", "stable beta"] {
+            assert!(Toolchain::detect(name, Path::new(".")).is_err(), "{name}");
+        }
+    }
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be monotonic")
+            .as_nanos();
+        std::env::temp_dir().join(format!("xtask-{prefix}-{nanos}"))
+    }
+}
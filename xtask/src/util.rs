@@ -1,5 +1,5 @@
 use std::{
-    fmt,
+    fmt, fs,
     path::Path,
     process::{Command, ExitStatus},
 };
@@ -53,6 +53,27 @@ pub fn run_capture_checked(program: &str, args: &[&str]) -> Result<String> {
         })
 }
 
+/// Save what `xtask run` knows about its results to `file`, for the `collect` of the
+/// criterion output `stamp` (see [`crate::criterion::output_stamp`]).
+pub fn save_last(workspace_root: &Path, file: &str, stamp: &str, contents: &str) -> Result<()> {
+    let path = workspace_root.join(file);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{LAST_STAMP_PREFIX}{stamp}\n{contents}"))?;
+    Ok(())
+}
+
+/// Contents of `file`, if [`save_last`] wrote it for the criterion output `stamp`; a
+/// later benchmark run, or one outside `xtask run`, makes it stale.
+pub fn load_last(workspace_root: &Path, file: &str, stamp: Option<&str>) -> Option<String> {
+    let raw = fs::read_to_string(workspace_root.join(file)).ok()?;
+    let (header, contents) = raw.split_once('\n')?;
+    (header.strip_prefix(LAST_STAMP_PREFIX)? == stamp?).then(|| contents.to_owned())
+}
+
+const LAST_STAMP_PREFIX: &str = "# criterion output: ";
+
 pub fn slugify(input: &str) -> String {
     let mut out = String::with_capacity(input.len());
     let mut start = true;
@@ -76,6 +97,30 @@ pub fn slugify(input: &str) -> String {
     out
 }
 
+/// Format a byte count with binary units, e.g. `4 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024 && value.is_multiple_of(1024) && unit + 1 < UNITS.len() {
+        value /= 1024;
+        unit += 1;
+    }
+    format!("{value} {}", UNITS[unit])
+}
+
+/// Format a byte rate with binary units, e.g. `12.34 GiB/s`.
+pub fn format_bytes_rate(bytes_per_sec: f64) -> String {
+    const UNITS: &[&str] = &["B/s", "KiB/s", "MiB/s", "GiB/s", "TiB/s"];
+    let mut value = bytes_per_sec;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
 fn format_command(program: &str, args: &[&str]) -> String {
     if args.is_empty() {
         return program.to_owned();
@@ -131,13 +176,30 @@ impl std::error::Error for CommandCaptureError {}
 
 #[cfg(test)]
 mod tests {
-    use super::{run_capture, run_capture_checked, slugify};
+    use super::{format_bytes, format_bytes_rate, run_capture, run_capture_checked, slugify};
 
     #[test]
     fn slugify_merges_separators() {
         assert_eq!(slugify(" Apple  M1 -- Pro "), "apple-m1-pro");
     }
 
+    #[test]
+    fn format_bytes_uses_exact_binary_unit() {
+        assert_eq!(format_bytes(16), "16 B");
+        assert_eq!(format_bytes(4096), "4 KiB");
+        assert_eq!(format_bytes(1 << 28), "256 MiB");
+        assert_eq!(format_bytes(1536), "1536 B");
+    }
+
+    #[test]
+    fn format_bytes_rate_picks_binary_unit() {
+        assert_eq!(format_bytes_rate(512.0), "512.00 B/s");
+        assert_eq!(
+            format_bytes_rate(3.0 * 1024.0 * 1024.0 * 1024.0),
+            "3.00 GiB/s"
+        );
+    }
+
     #[test]
     fn run_capture_checked_includes_stderr() {
         let err = run_capture_checked("sh", &["-c", "echo oops >&2; exit 7"])
@@ -0,0 +1,225 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    metadata::Ini,
+    scope::Scope,
+    util::{Result, load_last, run_capture_checked, save_last},
+};
+
+/// Where `xtask run` stores the versions its benchmarks were built with, for `collect`.
+const LAST_VERSIONS_FILE: &str = "target/xtask/versions.ini";
+
+/// Versions locked in `Cargo.lock` for the registry dependencies of each benchmark crate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LibraryVersions(BTreeMap<Scope, BTreeMap<String, String>>);
+
+impl LibraryVersions {
+    /// Resolve versions through `cargo metadata`, which reads `Cargo.lock`.
+    pub fn resolve(workspace_root: &Path) -> Result<Self> {
+        let manifest = workspace_root.join("Cargo.toml");
+        let manifest = manifest.to_string_lossy();
+        let raw = run_capture_checked("cargo", &[
+            "metadata",
+            "--format-version",
+            "1",
+            "--manifest-path",
+            &manifest,
+        ])?;
+        let metadata: Metadata = serde_json::from_str(&raw)
+            .map_err(|error| format!("failed to parse `cargo metadata` output: {error}"))?;
+        Ok(Self::from_metadata(&metadata))
+    }
+
+    fn from_metadata(metadata: &Metadata) -> Self {
+        let package = |id: &str| metadata.packages.iter().find(|package| package.id == id);
+        let mut versions = Self::default();
+        let Some(resolve) = &metadata.resolve else {
+            return versions;
+        };
+        for &scope in Scope::all() {
+            let Some(node) = resolve.nodes.iter().find(|node| {
+                package(&node.id).is_some_and(|package| package.name == scope.bench_crate())
+            }) else {
+                continue;
+            };
+            let crates = versions.0.entry(scope).or_default();
+            for dep in &node.deps {
+                if let Some(package) = package(&dep.pkg).filter(|package| package.source.is_some())
+                {
+                    crates.insert(package.name.clone(), package.version.clone());
+                }
+            }
+        }
+        versions
+    }
+
+    pub fn for_scope(&self, scope: Scope) -> Option<&BTreeMap<String, String>> {
+        self.0.get(&scope).filter(|crates| !crates.is_empty())
+    }
+
+    /// One `[dependencies.<scope>]` section of `environment.ini` per scope.
+    pub fn encode_ini(&self) -> String {
+        let mut out = String::new();
+        for (scope, crates) in &self.0 {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[dependencies.{scope}]\n"));
+            for (name, version) in crates {
+                out.push_str(&format!("{name} = {version}\n"));
+            }
+        }
+        out
+    }
+
+    /// Decode one `[dependencies.<scope>]` section written by [`Self::encode_ini`].
+    pub fn decode_section(
+        &mut self,
+        section: &str,
+        entries: &[(String, String)],
+    ) -> std::result::Result<(), String> {
+        let scope = section
+            .strip_prefix("dependencies.")
+            .and_then(|slug| Scope::all().iter().find(|scope| scope.slug() == slug))
+            .ok_or_else(|| format!("unknown section [{section}]"))?;
+        self.0
+            .entry(*scope)
+            .or_default()
+            .extend(entries.iter().cloned());
+        Ok(())
+    }
+
+    pub fn save_last(&self, workspace_root: &Path, stamp: &str) -> Result<()> {
+        save_last(
+            workspace_root,
+            LAST_VERSIONS_FILE,
+            stamp,
+            &self.encode_ini(),
+        )
+    }
+
+    /// Versions the `xtask run` that wrote the criterion output `stamp` was built with, if any.
+    pub fn load_last(workspace_root: &Path, stamp: Option<&str>) -> Option<Self> {
+        let raw = load_last(workspace_root, LAST_VERSIONS_FILE, stamp)?;
+        Self::decode_ini(&raw).ok()
+    }
+
+    fn decode_ini(input: &str) -> std::result::Result<Self, String> {
+        let mut versions = Self::default();
+        for (section, entries) in &Ini::parse(input)?.sections {
+            versions.decode_section(section, entries)?;
+        }
+        Ok(versions)
+    }
+
+    pub fn write_markdown(&self, writer: &mut impl Write, scope: Scope) -> std::io::Result<()> {
+        let Some(crates) = self.for_scope(scope) else {
+            return Ok(());
+        };
+        writeln!(writer)?;
+        writeln!(writer, "#### Library Versions")?;
+        writeln!(writer)?;
+        writeln!(writer, "| Crate | Version |")?;
+        writeln!(writer, "| --- | --- |")?;
+        for (name, version) in crates {
+            writeln!(writer, "| `{name}` | {version} |")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    resolve: Option<MetadataResolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    /// `None` for path dependencies such as `bench_support`.
+    source: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataResolve {
+    nodes: Vec<MetadataNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataNode {
+    id: String,
+    deps: Vec<MetadataDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataDep {
+    pkg: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LibraryVersions, Metadata};
+    use crate::scope::Scope;
+
+    #[test]
+    fn direct_dependencies_skip_path_crates() {
+        let metadata: Metadata = serde_json::from_str(
+            r#"{
+                "packages": [
+                    {"id": "hash", "name": "bench_hash", "version": "0.1.0", "source": null},
+                    {"id": "prng", "name": "bench_prng", "version": "0.1.0", "source": null},
+                    {"id": "support", "name": "bench_support", "version": "0.1.0", "source": null},
+                    {"id": "gx", "name": "gxhash", "version": "3.5.0", "source": "registry"},
+                    {"id": "ahash", "name": "ahash", "version": "0.8.0", "source": "registry"},
+                    {"id": "rand", "name": "rand", "version": "0.9.2", "source": "registry"}
+                ],
+                "resolve": {"nodes": [
+                    {"id": "hash", "deps": [{"pkg": "support"}, {"pkg": "gx"}]},
+                    {"id": "prng", "deps": [{"pkg": "rand"}]},
+                    {"id": "gx", "deps": [{"pkg": "ahash"}]}
+                ]}
+            }"#,
+        )
+        .expect("parse metadata");
+        let versions = LibraryVersions::from_metadata(&metadata);
+        let hash = versions.for_scope(Scope::Hash).expect("hash versions");
+        assert_eq!(hash.len(), 1);
+        assert_eq!(hash["gxhash"], "3.5.0");
+        let prng = versions.for_scope(Scope::Prng).expect("prng versions");
+        assert_eq!(prng["rand"], "0.9.2");
+    }
+
+    #[test]
+    fn ini_roundtrip_and_markdown() {
+        let entry = |name: &str, version: &str| vec![(name.to_owned(), version.to_owned())];
+        let mut versions = LibraryVersions::default();
+        versions
+            .decode_section("dependencies.hash", &entry("blake3", "1.8.3"))
+            .expect("hash");
+        versions
+            .decode_section("dependencies.prng", &entry("rand", "0.9.2"))
+            .expect("prng");
+        assert!(
+            versions
+                .decode_section("dependencies.unknown", &entry("foo", "1.0.0"))
+                .is_err()
+        );
+
+        let decoded = LibraryVersions::decode_ini(&versions.encode_ini()).expect("decode");
+        assert_eq!(decoded, versions);
+
+        let mut markdown = Vec::new();
+        versions
+            .write_markdown(&mut markdown, Scope::Hash)
+            .expect("write markdown");
+        let markdown = String::from_utf8(markdown).expect("utf-8");
+        assert!(markdown.contains("#### Library Versions"));
+        assert!(markdown.contains("| `blake3` | 1.8.3 |"));
+        assert!(!markdown.contains("rand"));
+    }
+}